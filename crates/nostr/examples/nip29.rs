@@ -36,6 +36,7 @@ fn main() -> Result<()> {
         picture: Some(Url::parse("https://rust-lang.org/logo.png")?),
         privacy: Privacy::Public,
         closed: AccessModel::Closed,
+        ..Default::default()
     };
 
     let create_event = EventBuilder::group_create(group_id.clone(), metadata.clone())
@@ -84,7 +85,7 @@ fn main() -> Result<()> {
     // ========================================
     println!("=== Sending Group Message ===");
 
-    let message_event = EventBuilder::group_message(group_id.clone(), "Hello, Rust community!")
+    let message_event = EventBuilder::group_message(group_id.clone(), "Hello, Rust community!")?
         .sign_with_keys(&member_keys)?;
 
     println!("Group message event:");
@@ -99,8 +100,8 @@ fn main() -> Result<()> {
 
     let previous_ids = vec![create_event.id, join_event.id];
 
-    let message_with_refs =
-        EventBuilder::group_message(group_id.clone(), "Replying to previous messages")
+    let _message_with_refs =
+        EventBuilder::group_message(group_id.clone(), "Replying to previous messages")?
             .with_previous_events(previous_ids.clone())
             .sign_with_keys(&member_keys)?;
 
@@ -119,6 +120,7 @@ fn main() -> Result<()> {
         picture: metadata.picture.clone(),
         privacy: Privacy::Public,
         closed: AccessModel::Open, // Changed to open
+        ..Default::default()
     };
 
     let edit_metadata_event =
@@ -197,7 +199,7 @@ fn main() -> Result<()> {
     println!("=== Relay-Generated Metadata Events ===");
 
     // Group metadata (kind 39000)
-    let metadata_event = EventBuilder::group_metadata(group_id.clone(), metadata.clone())
+    let _metadata_event = EventBuilder::group_metadata(group_id.clone(), metadata.clone())
         .sign_with_keys(&admin_keys)?;
 
     println!("Group metadata event (kind 39000):");
@@ -222,7 +224,7 @@ fn main() -> Result<()> {
         .add_member(admin_keys.public_key())
         .add_member(member_keys.public_key());
 
-    let members_event = EventBuilder::group_members(group_id.clone(), members)
+    let _members_event = EventBuilder::group_members(group_id.clone(), members)
         .sign_with_keys(&admin_keys)?;
 
     println!("Group members event (kind 39002):");
@@ -235,7 +237,7 @@ fn main() -> Result<()> {
         .add_role(Role::with_description("moderator", "Can moderate messages"))
         .add_role(Role::new("member"));
 
-    let roles_event = EventBuilder::group_roles(group_id.clone(), roles)
+    let _roles_event = EventBuilder::group_roles(group_id.clone(), roles)
         .sign_with_keys(&admin_keys)?;
 
     println!("Group roles event (kind 39003):");
@@ -21,6 +21,7 @@ use secp256k1::rand::{CryptoRng, Rng};
 use secp256k1::{Secp256k1, Signing, Verification};
 use serde_json::{json, Value};
 
+use crate::nips::nip29;
 use crate::nips::nip62::VanishTarget;
 use crate::prelude::*;
 
@@ -57,6 +58,8 @@ pub enum Error {
     NIP04(nip04::Error),
     /// NIP21 error
     NIP21(nip21::Error),
+    /// NIP29 error
+    NIP29(nip29::Error),
     /// NIP44 error
     #[cfg(all(feature = "std", feature = "nip44"))]
     NIP44(nip44::Error),
@@ -89,6 +92,7 @@ impl fmt::Display for Error {
             #[cfg(feature = "nip04")]
             Self::NIP04(e) => e.fmt(f),
             Self::NIP21(e) => e.fmt(f),
+            Self::NIP29(e) => e.fmt(f),
             #[cfg(all(feature = "std", feature = "nip44"))]
             Self::NIP44(e) => e.fmt(f),
             Self::NIP58(e) => e.fmt(f),
@@ -140,6 +144,12 @@ impl From<nip21::Error> for Error {
     }
 }
 
+impl From<nip29::Error> for Error {
+    fn from(e: nip29::Error) -> Self {
+        Self::NIP29(e)
+    }
+}
+
 #[cfg(all(feature = "std", feature = "nip44"))]
 impl From<nip44::Error> for Error {
     fn from(e: nip44::Error) -> Self {
@@ -235,6 +245,15 @@ impl EventBuilder {
         self
     }
 
+    /// Set a custom `created_at` UNIX timestamp
+    ///
+    /// Same as [`EventBuilder::custom_created_at`]: useful to build reproducible group events
+    /// or to import historical ones.
+    #[inline]
+    pub fn custom_timestamp(self, timestamp: Timestamp) -> Self {
+        self.custom_created_at(timestamp)
+    }
+
     /// Set POW difficulty
     ///
     /// Only values `> 0` are accepted!
@@ -938,6 +957,10 @@ impl EventBuilder {
 
     /// Create a group message (kind 9)
     ///
+    /// The `h` tag carries the bare group ID.
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `content` is empty or whitespace-only.
+    ///
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
     ///
     /// # Example
@@ -949,17 +972,170 @@ impl EventBuilder {
     /// # let keys = Keys::generate();
     /// let relay_url = Url::parse("wss://relay.example.com")?;
     /// let group_id = GroupId::new(relay_url, "rust-devs".to_string())?;
-    /// let event = EventBuilder::group_message(group_id, "Hello group!")
+    /// let event = EventBuilder::group_message(group_id, "Hello group!")?
     ///     .sign_with_keys(&keys)?;
     /// # Ok(())
     /// # }
     /// ```
+    pub fn group_message<S>(
+        group_id: crate::nips::nip29::GroupId,
+        content: S,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        Self::group_message_with_style(group_id, content, nip29::GroupTagStyle::H)
+    }
+
+    /// Create a group message (kind 9) replying to another message of the group
+    ///
+    /// Adds an `e` tag with the `reply` marker pointing at `reply_to`.
+    /// Can be combined with [`EventBuilder::with_previous_events`].
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `content` is empty or whitespace-only.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_message_reply<S>(
+        group_id: crate::nips::nip29::GroupId,
+        content: S,
+        reply_to: EventId,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        let reply: Tag = Tag::from_standardized_without_cell(TagStandard::Event {
+            event_id: reply_to,
+            relay_url: None,
+            marker: Some(Marker::Reply),
+            public_key: None,
+            uppercase: false,
+        });
+        Ok(Self::group_message(group_id, content)?.tag(reply))
+    }
+
     #[inline]
-    pub fn group_message<S>(group_id: crate::nips::nip29::GroupId, content: S) -> Self
+    pub(crate) fn group_chat_message<S>(group_id: crate::nips::nip29::GroupId, content: S) -> Self
     where
         S: Into<String>,
     {
-        Self::new(Kind::ChatMessage, content).tag(Tag::group_id(group_id.to_tag_value()))
+        Self::new(Kind::ChatMessage, content).tag(Tag::group_id(group_id.id))
+    }
+
+    /// Create a group message (kind 9) using a specific group tag style
+    ///
+    /// Use [`GroupTagStyle::G`](crate::nips::nip29::GroupTagStyle::G) for relays that expect a `g`
+    /// tag instead of `h`.
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `content` is empty or whitespace-only.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_message_with_style<S>(
+        group_id: crate::nips::nip29::GroupId,
+        content: S,
+        style: crate::nips::nip29::GroupTagStyle,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        let content: String = content.into();
+
+        if content.trim().is_empty() {
+            return Err(nip29::Error::EmptyContent);
+        }
+
+        Ok(Self::new(Kind::ChatMessage, content).tag(style.to_tag(&group_id)))
+    }
+
+    /// Create a group announcement (kind 9)
+    ///
+    /// A group message carrying an `announcement` tag, so clients can render it differently
+    /// (e.g., pinned or highlighted).
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `content` is empty or whitespace-only.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    #[inline]
+    pub fn group_announcement<S>(
+        group_id: crate::nips::nip29::GroupId,
+        content: S,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        Ok(Self::group_message(group_id, content)?.tag(Tag::custom(
+            TagKind::Custom("announcement".into()),
+            Vec::<String>::new(),
+        )))
+    }
+
+    /// Create a group poll (kind 9)
+    ///
+    /// A group message with the question as content and a `poll_option` tag
+    /// (`["poll_option", "<index>", "<option>"]`) for each option.
+    ///
+    /// Use [`parse_poll`](crate::nips::nip29::parse_poll) to parse it.
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `question` is empty or whitespace-only.
+    pub fn group_poll<S>(
+        group_id: crate::nips::nip29::GroupId,
+        question: S,
+        options: &[&str],
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        let options = options.iter().enumerate().map(|(index, option)| {
+            Tag::custom(
+                TagKind::Custom("poll_option".into()),
+                [index.to_string(), option.to_string()],
+            )
+        });
+        Ok(Self::group_message(group_id, question)?.tags(options))
+    }
+
+    /// Create a group welcome message (kind 9)
+    ///
+    /// A group message mentioning a newly approved member with a `p` tag,
+    /// to confirm that its membership was approved.
+    ///
+    /// Returns [`nip29::Error::EmptyContent`] if `message` is empty or whitespace-only.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    #[inline]
+    pub fn group_welcome<S>(
+        group_id: crate::nips::nip29::GroupId,
+        public_key: PublicKey,
+        message: S,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        Ok(Self::group_message(group_id, message)?.tag(Tag::public_key(public_key)))
+    }
+
+    /// Create a reaction to a group message (kind 7)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    #[inline]
+    pub fn group_reaction(
+        group_id: crate::nips::nip29::GroupId,
+        message_id: EventId,
+        reaction: &str,
+    ) -> Self {
+        Self::new(Kind::Reaction, reaction)
+            .tags([Tag::group_id(group_id.id), Tag::event(message_id)])
+    }
+
+    /// Group message acknowledgement (ephemeral)
+    ///
+    /// A read receipt for delivery tracking, referencing the message with an `e` tag.
+    ///
+    /// This isn't part of NIP-29: support is optional and relay-specific.
+    /// Check [`GROUP_ACK_KIND`](crate::nips::nip29::GROUP_ACK_KIND).
+    #[inline]
+    pub fn group_ack(group_id: crate::nips::nip29::GroupId, message_id: EventId) -> Self {
+        Self::new(Kind::from(crate::nips::nip29::GROUP_ACK_KIND), "")
+            .tags([Tag::group_id(group_id.id), Tag::event(message_id)])
     }
 
     /// Add timeline references (previous events)
@@ -977,6 +1153,42 @@ impl EventBuilder {
         self
     }
 
+    /// Attach a moderation reason (`reason` tag)
+    ///
+    /// Meant for moderation events (e.g. [`EventBuilder::group_remove_user`] or
+    /// [`EventBuilder::group_edit_metadata`]), so that the reason can be shown in moderation
+    /// logs. No tag is added if `reason` is `None`.
+    pub fn with_reason<S>(self, reason: Option<S>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.tag_maybe(
+            reason.map(|reason| Tag::custom(TagKind::Custom("reason".into()), [reason.into()])),
+        )
+    }
+
+    /// Mention users (`p` tags), so that clients can notify them
+    ///
+    /// Duplicated public keys, including the ones already tagged, are added only once.
+    pub fn with_mentions(mut self, public_keys: Vec<PublicKey>) -> Self {
+        for public_key in public_keys.into_iter() {
+            if !self.tags.public_keys().any(|pk| pk == &public_key) {
+                self = self.tag(Tag::public_key(public_key));
+            }
+        }
+        self
+    }
+
+    /// Add a subject line (`subject` tag)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/14.md>
+    #[inline]
+    pub fn with_subject(self, subject: &str) -> Self {
+        self.tag(Tag::from_standardized_without_cell(TagStandard::Subject(
+            subject.to_string(),
+        )))
+    }
+
     /// Join request (kind 9021)
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
@@ -992,7 +1204,7 @@ impl EventBuilder {
             Kind::GroupJoinRequest,
             message.map(|s| s.into()).unwrap_or_default(),
         )
-        .tag(Tag::group_id(group_id.to_tag_value()))
+        .tag(Tag::group_id(group_id.id))
     }
 
     /// Join request with invite code
@@ -1003,10 +1215,8 @@ impl EventBuilder {
     where
         S: Into<String>,
     {
-        Self::new(Kind::GroupJoinRequest, "").tags([
-            Tag::group_id(group_id.to_tag_value()),
-            Tag::invite_code(code.into()),
-        ])
+        Self::new(Kind::GroupJoinRequest, "")
+            .tags([Tag::group_id(group_id.id), Tag::invite_code(code.into())])
     }
 
     /// Leave request (kind 9022)
@@ -1014,7 +1224,7 @@ impl EventBuilder {
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
     #[inline]
     pub fn group_leave_request(group_id: crate::nips::nip29::GroupId) -> Self {
-        Self::new(Kind::GroupLeaveRequest, "").tag(Tag::group_id(group_id.to_tag_value()))
+        Self::new(Kind::GroupLeaveRequest, "").tag(Tag::group_id(group_id.id))
     }
 
     /// Add or update user with roles (kind 9000)
@@ -1025,10 +1235,8 @@ impl EventBuilder {
         public_key: PublicKey,
         roles: Vec<String>,
     ) -> Self {
-        let mut builder = Self::new(Kind::GroupPutUser, "").tags([
-            Tag::group_id(group_id.to_tag_value()),
-            Tag::public_key(public_key),
-        ]);
+        let mut builder = Self::new(Kind::GroupPutUser, "")
+            .tags([Tag::group_id(group_id.id), Tag::public_key(public_key)]);
 
         for role in roles {
             builder = builder.tag(Tag::role(role, None::<String>));
@@ -1037,6 +1245,48 @@ impl EventBuilder {
         builder
     }
 
+    /// Assign the same role to multiple users (kind 9000)
+    ///
+    /// Each `p` tag is immediately followed by the `role` tag, mirroring the admins list layout.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_assign_role(
+        group_id: crate::nips::nip29::GroupId,
+        role: &str,
+        public_keys: &[PublicKey],
+    ) -> Self {
+        let mut tags: Vec<Tag> = Vec::with_capacity(1 + public_keys.len() * 2);
+        tags.push(Tag::group_id(group_id.id));
+
+        for public_key in public_keys.iter() {
+            tags.push(Tag::public_key(*public_key));
+            tags.push(Tag::role(role, None));
+        }
+
+        Self::new(Kind::GroupPutUser, "").tags(tags)
+    }
+
+    /// Add or update multiple users with roles (kind 9000)
+    ///
+    /// Users are added in the given order. Each `p` tag is immediately followed by the `role`
+    /// tags of that user, mirroring the admins list layout.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_put_users(
+        group_id: crate::nips::nip29::GroupId,
+        users: Vec<(PublicKey, Vec<String>)>,
+    ) -> Self {
+        let mut tags: Vec<Tag> = Vec::with_capacity(1 + users.len());
+        tags.push(Tag::group_id(group_id.id));
+
+        for (public_key, roles) in users.into_iter() {
+            tags.push(Tag::public_key(public_key));
+            tags.extend(roles.into_iter().map(|role| Tag::role(role, None)));
+        }
+
+        Self::new(Kind::GroupPutUser, "").tags(tags)
+    }
+
     /// Remove user (kind 9001)
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
@@ -1046,7 +1296,7 @@ impl EventBuilder {
         public_key: PublicKey,
     ) -> Self {
         Self::new(Kind::GroupRemoveUser, "").tags([
-            Tag::group_id(group_id.to_tag_value()),
+            Tag::group_id(group_id.id),
             Tag::public_key(public_key),
         ])
     }
@@ -1058,20 +1308,59 @@ impl EventBuilder {
         group_id: crate::nips::nip29::GroupId,
         metadata: crate::nips::nip29::GroupMetadata,
     ) -> Self {
-        let mut builder =
-            Self::new(Kind::GroupEditMetadata, "").tag(Tag::group_id(group_id.to_tag_value()));
+        let mut builder = Self::new(Kind::GroupEditMetadata, "").tag(Tag::group_id(group_id.id));
 
         builder = builder.tags(Vec::<Tag>::from(metadata));
         builder
     }
 
+    /// Edit only some fields of the group metadata (kind 9002)
+    ///
+    /// Only the `Some` fields are emitted, so the other ones are left untouched by the relay.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_edit_metadata_partial(
+        group_id: crate::nips::nip29::GroupId,
+        name: Option<String>,
+        about: Option<String>,
+        picture: Option<Url>,
+        privacy: Option<crate::nips::nip29::Privacy>,
+        closed: Option<crate::nips::nip29::AccessModel>,
+    ) -> Self {
+        let privacy: Option<Tag> =
+            privacy.map(|p| Tag::custom(TagKind::Custom("privacy".into()), [p.as_str()]));
+        let closed: Option<Tag> =
+            closed.map(|c| Tag::custom(TagKind::Custom("closed".into()), [c.as_str()]));
+
+        Self::new(Kind::GroupEditMetadata, "")
+            .tag(Tag::group_id(group_id.id))
+            .tag_maybe(name.map(|name| Tag::custom(TagKind::Name, [name])))
+            .tag_maybe(about.map(|about| Tag::custom(TagKind::Description, [about])))
+            .tag_maybe(picture.map(|url| Tag::custom(TagKind::Image, [url.to_string()])))
+            .tag_maybe(privacy)
+            .tag_maybe(closed)
+    }
+
+    /// Set or clear the group picture (kind 9002)
+    ///
+    /// `None` clears the picture by emitting an empty `image` tag.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_set_picture(group_id: crate::nips::nip29::GroupId, picture: Option<Url>) -> Self {
+        let picture: String = picture.map(|url| url.to_string()).unwrap_or_default();
+        Self::new(Kind::GroupEditMetadata, "").tags([
+            Tag::group_id(group_id.id),
+            Tag::custom(TagKind::Image, [picture]),
+        ])
+    }
+
     /// Delete event (kind 9005)
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
     #[inline]
     pub fn group_delete_event(group_id: crate::nips::nip29::GroupId, event_id: EventId) -> Self {
         Self::new(Kind::GroupDeleteEvent, "").tags([
-            Tag::group_id(group_id.to_tag_value()),
+            Tag::group_id(group_id.id),
             Tag::event(event_id),
         ])
     }
@@ -1083,8 +1372,7 @@ impl EventBuilder {
         group_id: crate::nips::nip29::GroupId,
         metadata: crate::nips::nip29::GroupMetadata,
     ) -> Self {
-        let mut builder =
-            Self::new(Kind::GroupCreate, "").tag(Tag::group_id(group_id.to_tag_value()));
+        let mut builder = Self::new(Kind::GroupCreate, "").tag(Tag::group_id(group_id.id));
 
         builder = builder.tags(Vec::<Tag>::from(metadata));
         builder
@@ -1095,7 +1383,7 @@ impl EventBuilder {
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
     #[inline]
     pub fn group_delete(group_id: crate::nips::nip29::GroupId) -> Self {
-        Self::new(Kind::GroupDelete, "").tag(Tag::group_id(group_id.to_tag_value()))
+        Self::new(Kind::GroupDelete, "").tag(Tag::group_id(group_id.id))
     }
 
     /// Create invite code (kind 9009)
@@ -1103,7 +1391,64 @@ impl EventBuilder {
     /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
     #[inline]
     pub fn group_create_invite(group_id: crate::nips::nip29::GroupId) -> Self {
-        Self::new(Kind::GroupCreateInvite, "").tag(Tag::group_id(group_id.to_tag_value()))
+        Self::new(Kind::GroupCreateInvite, "").tag(Tag::group_id(group_id.id))
+    }
+
+    /// Create invite with a specific code (kind 9009)
+    ///
+    /// Adds a `code` tag and, if provided, an `expiration` tag
+    /// ([NIP-40](https://github.com/nostr-protocol/nips/blob/master/40.md)),
+    /// so that relays can invalidate the invite.
+    ///
+    /// Returns an error if the code is not URL-safe (see [`nip29::validate_invite_code`]).
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_create_invite_with_code<S>(
+        group_id: crate::nips::nip29::GroupId,
+        code: S,
+        expiration: Option<Timestamp>,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        let code: String = code.into();
+        nip29::validate_invite_code(&code)?;
+
+        let mut builder: Self = Self::group_create_invite(group_id).tag(Tag::invite_code(code));
+
+        if let Some(expiration) = expiration {
+            builder = builder.tag(Tag::expiration(expiration));
+        }
+
+        Ok(builder)
+    }
+
+    /// Revoke an invite code (kind 9005)
+    ///
+    /// NIP-29 doesn't define a revocation event, so the invite is revoked by deleting
+    /// the create-invite event (kind 9009) with a regular delete-event: the `e` tag references
+    /// the invite event, like [`EventBuilder::group_delete_event`].
+    /// The `code` tag is added too, for relays that index invites by code.
+    ///
+    /// Returns an error if the code is not URL-safe (see [`nip29::validate_invite_code`]).
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/29.md>
+    pub fn group_revoke_invite<S>(
+        group_id: crate::nips::nip29::GroupId,
+        invite_id: EventId,
+        code: S,
+    ) -> Result<Self, nip29::Error>
+    where
+        S: Into<String>,
+    {
+        let code: String = code.into();
+        nip29::validate_invite_code(&code)?;
+
+        Ok(Self::new(Kind::GroupDeleteEvent, "").tags([
+            Tag::group_id(group_id.id),
+            Tag::event(invite_id),
+            Tag::invite_code(code),
+        ]))
     }
 
     /// Group metadata event (kind 39000, addressable)
@@ -1117,7 +1462,7 @@ impl EventBuilder {
     ) -> Self {
         let mut builder = Self::new(Kind::GroupMetadata, "").tags([
             Tag::identifier(group_id.id.clone()),
-            Tag::group_id(group_id.to_tag_value()),
+            Tag::group_id(group_id.id),
         ]);
 
         builder = builder.tags(Vec::<Tag>::from(metadata));
@@ -2526,6 +2871,7 @@ mod tests {
         let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
 
         let event = EventBuilder::group_message(group_id.clone(), "Hello group!")
+            .unwrap()
             .sign_with_keys(&keys)
             .unwrap();
 
@@ -2533,10 +2879,20 @@ mod tests {
         assert_eq!(event.content, "Hello group!");
 
         // Check for group_id tag
-        let has_h_tag = event.tags.iter().any(|t| {
-            t.as_slice()[0] == "h" && t.as_slice()[1] == "wss://relay.example.com'test-group"
-        });
-        assert!(has_h_tag);
+        assert_eq!(
+            event.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(group_id.id.as_str())
+        );
+
+        // Empty content
+        assert_eq!(
+            EventBuilder::group_message(group_id.clone(), "").unwrap_err(),
+            nip29::Error::EmptyContent
+        );
+        assert_eq!(
+            EventBuilder::group_message(group_id, " \n\t").unwrap_err(),
+            nip29::Error::EmptyContent
+        );
     }
 
     #[test]
@@ -2642,10 +2998,12 @@ mod tests {
         let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
 
         let event1 = EventBuilder::group_message(group_id.clone(), "First message")
+            .unwrap()
             .sign_with_keys(&keys)
             .unwrap();
 
         let event2 = EventBuilder::group_message(group_id, "Second message")
+            .unwrap()
             .with_previous_events(vec![event1.id])
             .sign_with_keys(&keys)
             .unwrap();
@@ -2677,6 +3035,7 @@ mod tests {
             picture: Some(Url::parse("https://example.com/pic.png").unwrap()),
             privacy: Privacy::Public,
             closed: AccessModel::Closed,
+            ..Default::default()
         };
 
         let meta_event = EventBuilder::group_metadata(group_id.clone(), metadata)
@@ -2722,6 +3081,500 @@ mod tests {
 
         assert_eq!(roles_event.kind, Kind::GroupRoles);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_announcement() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event = EventBuilder::group_announcement(group_id.clone(), "Meeting moved to Friday")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::ChatMessage);
+        assert_eq!(event.content, "Meeting moved to Friday");
+
+        let has_announcement_tag = event.tags.iter().any(|t| t.as_slice() == ["announcement"]);
+        assert!(has_announcement_tag);
+
+        let has_h_tag = event
+            .tags
+            .iter()
+            .any(|t| t.as_slice()[0] == "h" && t.as_slice()[1] == "test-group");
+        assert!(has_h_tag);
+
+        // Empty content
+        assert_eq!(
+            EventBuilder::group_announcement(group_id, " ").unwrap_err(),
+            nip29::Error::EmptyContent
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_tag_style() {
+        use crate::nips::nip29::{GroupId, GroupTagStyle};
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+        let expected = "test-group";
+
+        // H
+        let event =
+            EventBuilder::group_message_with_style(group_id.clone(), "Hello", GroupTagStyle::H)
+                .unwrap()
+                .sign_with_keys(&keys)
+                .unwrap();
+        assert_eq!(event.tags.find(TagKind::h()).unwrap().as_slice()[0], "h");
+        assert_eq!(GroupTagStyle::H.extract(&event), Some(expected));
+        assert_eq!(GroupTagStyle::G.extract(&event), None);
+
+        // G
+        let event =
+            EventBuilder::group_message_with_style(group_id.clone(), "Hello", GroupTagStyle::G)
+                .unwrap()
+                .sign_with_keys(&keys)
+                .unwrap();
+        assert_eq!(
+            event
+                .tags
+                .find(TagKind::single_letter(Alphabet::G, false))
+                .unwrap()
+                .as_slice()[0],
+            "g"
+        );
+        assert_eq!(GroupTagStyle::G.extract(&event), Some(expected));
+        assert_eq!(GroupTagStyle::H.extract(&event), None);
+
+        // Empty content
+        assert_eq!(
+            EventBuilder::group_message_with_style(group_id, "", GroupTagStyle::G).unwrap_err(),
+            nip29::Error::EmptyContent
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_welcome() {
+        use crate::nips::nip29::GroupId;
+
+        let admin_keys = Keys::generate();
+        let member_keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event =
+            EventBuilder::group_welcome(group_id.clone(), member_keys.public_key(), "Welcome!")
+                .unwrap()
+                .sign_with_keys(&admin_keys)
+                .unwrap();
+
+        assert_eq!(event.kind, Kind::ChatMessage);
+        assert_eq!(event.content, "Welcome!");
+        assert_eq!(
+            event.tags.public_keys().copied().collect::<Vec<_>>(),
+            vec![member_keys.public_key()]
+        );
+        assert!(event.tags.find(TagKind::h()).is_some());
+
+        // Empty content
+        assert_eq!(
+            EventBuilder::group_welcome(group_id, member_keys.public_key(), "").unwrap_err(),
+            nip29::Error::EmptyContent
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_top_level_group() {
+        use crate::nips::nip29::{GroupId, GroupMetadata};
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::top_level(relay_url).unwrap();
+
+        let event = EventBuilder::group_metadata(group_id, GroupMetadata::default())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.tags.identifier(), Some("_"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_set_picture() {
+        use crate::nips::nip29::{GroupId, GroupMetadata};
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+        let picture = Url::parse("https://example.com/pic.png").unwrap();
+
+        // Set
+        let event = EventBuilder::group_set_picture(group_id.clone(), Some(picture.clone()))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::GroupEditMetadata);
+        assert_eq!(
+            event.tags.find(TagKind::Image).unwrap().content(),
+            Some(picture.as_str())
+        );
+        let metadata = GroupMetadata::from_tags(event.tags.iter()).unwrap();
+        assert_eq!(metadata.picture, Some(picture));
+
+        // Clear
+        let event = EventBuilder::group_set_picture(group_id, None)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.tags.find(TagKind::Image).unwrap().content(), Some(""));
+        let metadata = GroupMetadata::from_tags(event.tags.iter()).unwrap();
+        assert_eq!(metadata.picture, None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_assign_role() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let pk1 = Keys::generate().public_key();
+        let pk2 = Keys::generate().public_key();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event = EventBuilder::group_assign_role(group_id, "moderator", &[pk1, pk2])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::GroupPutUser);
+
+        let tags: Vec<&[String]> = event.tags.iter().skip(1).map(|t| t.as_slice()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                &[String::from("p"), pk1.to_hex()][..],
+                &[String::from("role"), String::from("moderator")][..],
+                &[String::from("p"), pk2.to_hex()][..],
+                &[String::from("role"), String::from("moderator")][..],
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_ack() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let message = EventBuilder::group_message(group_id.clone(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let ack = EventBuilder::group_ack(group_id.clone(), message.id)
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert!(ack.kind.is_ephemeral());
+        assert_eq!(
+            ack.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(group_id.id.as_str())
+        );
+        assert_eq!(
+            ack.tags.event_ids().copied().collect::<Vec<_>>(),
+            vec![message.id]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_edit_metadata_partial() {
+        use crate::nips::nip29::{GroupId, GroupMetadata, Privacy};
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event = EventBuilder::group_edit_metadata_partial(
+            group_id,
+            Some("New name".to_string()),
+            None,
+            None,
+            Some(Privacy::Private),
+            None,
+        )
+        .sign_with_keys(&keys)
+        .unwrap();
+
+        assert_eq!(event.kind, Kind::GroupEditMetadata);
+        // `h`, `name` and `privacy` tags only
+        assert_eq!(event.tags.len(), 3);
+        assert!(event.tags.find(TagKind::Description).is_none());
+        assert!(event.tags.find(TagKind::Custom("closed".into())).is_none());
+
+        let metadata = GroupMetadata::from_tags(event.tags.iter()).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("New name"));
+        assert_eq!(metadata.privacy, Privacy::Private);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_reaction() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let message = EventBuilder::group_message(group_id.clone(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let reaction = EventBuilder::group_reaction(group_id.clone(), message.id, "🤙")
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(reaction.kind, Kind::Reaction);
+        assert_eq!(reaction.content, "🤙");
+        assert_eq!(
+            reaction.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(group_id.id.as_str())
+        );
+        assert_eq!(
+            reaction.tags.event_ids().copied().collect::<Vec<_>>(),
+            vec![message.id]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_custom_timestamp() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+        let created_at = Timestamp::from_secs(1_700_000_000);
+
+        for builder in [
+            EventBuilder::group_message(group_id.clone(), "Hello").unwrap(),
+            EventBuilder::group_join_request(group_id.clone(), None::<String>),
+            EventBuilder::group_delete(group_id),
+        ] {
+            let event = builder
+                .custom_timestamp(created_at)
+                .sign_with_keys(&keys)
+                .unwrap();
+            assert_eq!(event.created_at, created_at);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_message_reply() {
+        use crate::nips::nip29::{GroupId, GroupMessage};
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let parent = EventBuilder::group_message(group_id.clone(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let event = EventBuilder::group_message_reply(group_id.clone(), "Hi!", parent.id)
+            .unwrap()
+            .with_previous_events(vec![parent.id])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::ChatMessage);
+
+        let message = GroupMessage::try_from(&event).unwrap();
+        assert_eq!(message.group, group_id.id);
+        assert_eq!(message.reply(), Some(parent.id));
+        assert_eq!(message.previous(), [parent.id.to_hex()[..8].to_string()]);
+
+        assert!(EventBuilder::group_message_reply(group_id, "", parent.id).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_create_invite_with_code() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+        let expiration = Timestamp::from_secs(1_700_000_000);
+
+        let event = EventBuilder::group_create_invite_with_code(
+            group_id.clone(),
+            "INVITE123",
+            Some(expiration),
+        )
+        .unwrap()
+        .sign_with_keys(&keys)
+        .unwrap();
+
+        assert_eq!(event.kind, Kind::GroupCreateInvite);
+        assert_eq!(
+            event
+                .tags
+                .find(TagKind::Custom("code".into()))
+                .and_then(|t| t.content()),
+            Some("INVITE123")
+        );
+        assert_eq!(event.tags.expiration(), Some(&expiration));
+
+        let event = EventBuilder::group_create_invite_with_code(group_id.clone(), "abc", None)
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.tags.expiration(), None);
+
+        assert_eq!(
+            EventBuilder::group_create_invite_with_code(group_id, "not safe!", None).unwrap_err(),
+            nip29::Error::InvalidInviteCode(String::from("not safe!"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_put_users() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let pk1 = Keys::generate().public_key();
+        let pk2 = Keys::generate().public_key();
+        let pk3 = Keys::generate().public_key();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event = EventBuilder::group_put_users(
+            group_id,
+            vec![
+                (pk1, vec![String::from("admin"), String::from("moderator")]),
+                (pk2, Vec::new()),
+                (pk3, vec![String::from("moderator")]),
+            ],
+        )
+        .sign_with_keys(&keys)
+        .unwrap();
+
+        assert_eq!(event.kind, Kind::GroupPutUser);
+
+        let tags: Vec<&[String]> = event.tags.iter().skip(1).map(|t| t.as_slice()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                &[String::from("p"), pk1.to_hex()][..],
+                &[String::from("role"), String::from("admin")][..],
+                &[String::from("role"), String::from("moderator")][..],
+                &[String::from("p"), pk2.to_hex()][..],
+                &[String::from("p"), pk3.to_hex()][..],
+                &[String::from("role"), String::from("moderator")][..],
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_group_revoke_invite() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let invite = EventBuilder::group_create_invite(group_id.clone())
+            .sign_with_keys(&keys)
+            .unwrap();
+        let event = EventBuilder::group_revoke_invite(group_id.clone(), invite.id, "INVITE123")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::GroupDeleteEvent);
+        assert_eq!(
+            event.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(group_id.id.as_str())
+        );
+        assert_eq!(event.tags.event_ids().collect::<Vec<_>>(), vec![&invite.id]);
+        assert_eq!(
+            event
+                .tags
+                .find(TagKind::Custom("code".into()))
+                .and_then(|t| t.content()),
+            Some("INVITE123")
+        );
+
+        assert!(EventBuilder::group_revoke_invite(group_id, invite.id, "").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_with_mentions() {
+        use crate::nips::nip29::GroupId;
+
+        let keys = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+
+        let event = EventBuilder::group_welcome(group_id.clone(), alice, "Welcome!")
+            .unwrap()
+            .with_mentions(vec![bob, alice, bob])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(
+            event.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(group_id.id.as_str())
+        );
+        assert_eq!(
+            event.tags.public_keys().copied().collect::<Vec<_>>(),
+            vec![alice, bob]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_nip29_with_reason() {
+        use crate::nips::nip29::{GroupId, GroupMetadata};
+
+        let keys = Keys::generate();
+        let user = Keys::generate().public_key();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(relay_url, "test-group".to_string()).unwrap();
+        let reason = TagKind::Custom("reason".into());
+
+        let event = EventBuilder::group_remove_user(group_id.clone(), user)
+            .with_reason(Some("Spamming"))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::GroupRemoveUser);
+        assert_eq!(
+            event.tags.find(reason.clone()).and_then(|t| t.content()),
+            Some("Spamming")
+        );
+
+        let event = EventBuilder::group_edit_metadata(group_id, GroupMetadata::default())
+            .with_reason(None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::GroupEditMetadata);
+        assert_eq!(event.tags.find(reason), None);
+    }
 }
 
 #[cfg(bench)]
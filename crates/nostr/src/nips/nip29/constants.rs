@@ -4,6 +4,8 @@
 
 //! NIP-29: Constants
 
+use alloc::vec::Vec;
+
 use crate::Kind;
 
 /// NIP-29 Moderation event kinds
@@ -18,9 +20,43 @@ pub const NIP29_USER_KINDS: [u16; 2] = [9021, 9022];
 /// Special group ID for top-level relay-local discussion
 pub const TOP_LEVEL_GROUP_ID: &str = "_";
 
+/// Ephemeral kind used by some relays to acknowledge the delivery of group messages
+///
+/// Not part of NIP-29: support is optional and relay-specific.
+pub const GROUP_ACK_KIND: u16 = 29000;
+
+/// Bech32 prefix of shareable group identifiers
+pub const PREFIX_BECH32_GROUP: &str = "ngroup";
+
+/// Marker of the `p` tag carrying the group owner in the metadata event
+pub const OWNER_MARKER: &str = "owner";
+
+/// Minimum length of a timeline reference (`previous` tag value), in hex characters
+pub const MIN_PREVIOUS_REF_LEN: usize = 8;
+
+/// Maximum length of a role name, in characters
+pub const MAX_ROLE_NAME_LEN: usize = 64;
+
 /// Valid characters for group IDs: a-z, 0-9, -, _
 pub const GROUP_ID_PATTERN: &str = r"^[a-z0-9_-]+$";
 
+/// Kinds that imply a change of the addressable group state (kinds 39000-39003)
+///
+/// When one of these events is seen, clients should re-fetch the group metadata, admins,
+/// members and roles. Join and leave requests are included since relays may apply them
+/// automatically (e.g. in open groups).
+pub fn state_affecting_kinds() -> Vec<Kind> {
+    vec![
+        Kind::GroupPutUser,
+        Kind::GroupRemoveUser,
+        Kind::GroupEditMetadata,
+        Kind::GroupCreate,
+        Kind::GroupDelete,
+        Kind::GroupJoinRequest,
+        Kind::GroupLeaveRequest,
+    ]
+}
+
 impl Kind {
     /// Check if kind is a NIP-29 moderation event
     ///
@@ -95,4 +131,13 @@ mod tests {
         assert!(!Kind::from(1).is_group_event());
         assert!(!Kind::from(4).is_group_event());
     }
+
+    #[test]
+    fn test_state_affecting_kinds() {
+        let kinds = state_affecting_kinds();
+        assert!(kinds.contains(&Kind::from(9000)));
+        assert!(kinds.contains(&Kind::GroupEditMetadata));
+        assert!(!kinds.contains(&Kind::from(9)));
+        assert!(!kinds.contains(&Kind::GroupDeleteEvent));
+    }
 }
@@ -0,0 +1,242 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-29: Group context
+
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{Error, GroupId};
+#[cfg(feature = "std")]
+use crate::event::builder::Error as BuilderError;
+#[cfg(feature = "std")]
+use crate::{Event, Keys, PublicKey, UnsignedEvent};
+use crate::{EventBuilder, EventId};
+
+/// Default number of timeline references kept by [`GroupTimeline`]
+const DEFAULT_TIMELINE_CAPACITY: usize = 3;
+
+/// Tracker of the most recent events of a group timeline
+///
+/// Used to fill the `previous` tag of new messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTimeline {
+    capacity: usize,
+    recent: VecDeque<EventId>,
+}
+
+impl Default for GroupTimeline {
+    fn default() -> Self {
+        Self::new(DEFAULT_TIMELINE_CAPACITY)
+    }
+}
+
+impl GroupTimeline {
+    /// New timeline tracker keeping at most `capacity` event IDs
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recent: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Track a new event
+    ///
+    /// If the tracker is full, the oldest event is dropped.
+    pub fn push(&mut self, event_id: EventId) {
+        if self.capacity == 0 || self.recent.contains(&event_id) {
+            return;
+        }
+
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+
+        self.recent.push_back(event_id);
+    }
+
+    /// Get the tracked event IDs (oldest first)
+    #[inline]
+    pub fn recent(&self) -> Vec<EventId> {
+        self.recent.iter().copied().collect()
+    }
+}
+
+/// Group context
+///
+/// High-level API to build events for a specific group.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroupContext {
+    group_id: GroupId,
+}
+
+impl GroupContext {
+    /// New group context
+    #[inline]
+    pub fn new(group_id: GroupId) -> Self {
+        Self { group_id }
+    }
+
+    /// Get group ID
+    #[inline]
+    pub fn group_id(&self) -> &GroupId {
+        &self.group_id
+    }
+
+    /// Build a group message referencing the recent events of the timeline
+    pub fn post<S>(&self, content: S, recent: &GroupTimeline) -> Result<EventBuilder, Error>
+    where
+        S: Into<String>,
+    {
+        Ok(EventBuilder::group_message(self.group_id.clone(), content)?
+            .with_previous_events(recent.recent()))
+    }
+
+    /// Build and sign a group message (kind 9)
+    #[cfg(feature = "std")]
+    pub fn signed_message<S>(&self, content: S, keys: &Keys) -> Result<Event, BuilderError>
+    where
+        S: Into<String>,
+    {
+        EventBuilder::group_message(self.group_id.clone(), content)?.sign_with_keys(keys)
+    }
+
+    /// Build an unsigned group message (kind 9), to be signed externally
+    ///
+    /// Useful for hardware signers or FFI users that can't hand over the [`Keys`].
+    #[cfg(feature = "std")]
+    pub fn message_template<S>(
+        &self,
+        public_key: PublicKey,
+        content: S,
+    ) -> Result<UnsignedEvent, Error>
+    where
+        S: Into<String>,
+    {
+        Ok(EventBuilder::group_message(self.group_id.clone(), content)?.build(public_key))
+    }
+
+    /// Build and sign a join request (kind 9021)
+    #[cfg(feature = "std")]
+    pub fn signed_join_request<S>(
+        &self,
+        message: Option<S>,
+        keys: &Keys,
+    ) -> Result<Event, BuilderError>
+    where
+        S: Into<String>,
+    {
+        EventBuilder::group_join_request(self.group_id.clone(), message).sign_with_keys(keys)
+    }
+
+    /// Build and sign a leave request (kind 9022)
+    #[cfg(feature = "std")]
+    pub fn signed_leave_request(&self, keys: &Keys) -> Result<Event, BuilderError> {
+        EventBuilder::group_leave_request(self.group_id.clone()).sign_with_keys(keys)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Kind, TagKind, Url};
+
+    fn group_id() -> GroupId {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        GroupId::new(url, "test-group".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_group_timeline() {
+        let keys = Keys::generate();
+        let ctx = GroupContext::new(group_id());
+        let mut timeline = GroupTimeline::new(2);
+
+        let mut ids: Vec<EventId> = Vec::new();
+        for content in ["one", "two", "three"] {
+            let event = ctx
+                .post(content, &timeline)
+                .unwrap()
+                .sign_with_keys(&keys)
+                .unwrap();
+            timeline.push(event.id);
+            ids.push(event.id);
+        }
+
+        assert_eq!(timeline.recent(), vec![ids[1], ids[2]]);
+    }
+
+    #[test]
+    fn test_group_context_post() {
+        let keys = Keys::generate();
+        let ctx = GroupContext::new(group_id());
+        let mut timeline = GroupTimeline::default();
+
+        let first = ctx
+            .post("first", &timeline)
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        timeline.push(first.id);
+
+        let event = ctx
+            .post("second", &timeline)
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(
+            event.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(ctx.group_id().id.as_str())
+        );
+
+        let previous = event.tags.find(TagKind::Custom("previous".into())).unwrap();
+        assert_eq!(previous.as_slice()[1], first.id.to_hex()[..8]);
+    }
+
+    #[test]
+    fn test_group_context_signed_helpers() {
+        let keys = Keys::generate();
+        let ctx = GroupContext::new(group_id());
+        let event = ctx.signed_message("Hello", &keys).unwrap();
+        assert!(event.verify().is_ok());
+        assert_eq!(event.kind, Kind::ChatMessage);
+        assert_eq!(event.pubkey, keys.public_key());
+        assert_eq!(
+            event.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(ctx.group_id().id.as_str())
+        );
+
+        let event = ctx.signed_join_request(Some("Hi"), &keys).unwrap();
+        assert!(event.verify().is_ok());
+        assert_eq!(event.kind, Kind::GroupJoinRequest);
+
+        let event = ctx.signed_leave_request(&keys).unwrap();
+        assert!(event.verify().is_ok());
+        assert_eq!(event.kind, Kind::GroupLeaveRequest);
+    }
+
+    #[test]
+    fn test_group_context_message_template() {
+        let keys = Keys::generate();
+        let ctx = GroupContext::new(group_id());
+
+        let unsigned = ctx.message_template(keys.public_key(), "Hello").unwrap();
+        assert_eq!(unsigned.kind, Kind::ChatMessage);
+        assert_eq!(unsigned.pubkey, keys.public_key());
+        assert_eq!(
+            unsigned.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(ctx.group_id().id.as_str())
+        );
+
+        let event = unsigned.sign_with_keys(&keys).unwrap();
+        assert!(event.verify().is_ok());
+
+        assert_eq!(
+            ctx.message_template(keys.public_key(), "").unwrap_err(),
+            Error::EmptyContent
+        );
+    }
+}
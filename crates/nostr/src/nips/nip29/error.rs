@@ -7,6 +7,9 @@
 use alloc::string::String;
 use core::fmt;
 
+use super::Permission;
+use crate::{EventId, Kind, PublicKey};
+
 /// NIP-29 error
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
@@ -18,8 +21,66 @@ pub enum Error {
     InvalidAccessModel(String),
     /// Missing required tag
     MissingRequiredTag(String),
+    /// Invalid permission value
+    InvalidPermission(String),
     /// Invalid group identifier format (should be host'id)
     InvalidGroupIdentifier(String),
+    /// Bech32 encoding or decoding error
+    Bech32(String),
+    /// Invalid relay URL
+    InvalidRelayUrl(String),
+    /// Unexpected event kind
+    WrongKind(Kind),
+    /// Invalid group metadata
+    InvalidMetadata(String),
+    /// Invalid public key
+    InvalidPublicKey(String),
+    /// Invalid role definition
+    InvalidRole(String),
+    /// Role is not defined in the group roles
+    UndefinedRole(String),
+    /// Admin is missing from the members list
+    AdminNotMember(PublicKey),
+    /// Public key is not a group member
+    NotMember(PublicKey),
+    /// Event of a private group is not protected
+    UnprotectedEvent(EventId),
+    /// Events belong to different groups
+    GroupMismatch {
+        /// Expected group identifier
+        expected: String,
+        /// Found group identifier
+        found: String,
+    },
+    /// Timeline reference to an event of another group
+    CrossGroupReference(String),
+    /// Too many timeline references
+    TooManyPreviousRefs {
+        /// Number of references
+        count: usize,
+        /// Maximum allowed
+        max: usize,
+    },
+    /// Empty or whitespace-only content
+    EmptyContent,
+    /// Invalid invite code
+    InvalidInviteCode(String),
+    /// Invite code doesn't match
+    InviteCodeMismatch,
+    /// Event was not authored by the expected public key
+    UnexpectedAuthor {
+        /// Expected author
+        expected: PublicKey,
+        /// Actual author
+        found: PublicKey,
+    },
+    /// Author lacks the required permission
+    MissingPermission {
+        /// Author
+        public_key: PublicKey,
+        /// Required permission
+        permission: Permission,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -32,9 +93,43 @@ impl fmt::Display for Error {
             Self::InvalidPrivacy(msg) => write!(f, "Invalid privacy value: {msg}"),
             Self::InvalidAccessModel(msg) => write!(f, "Invalid access model value: {msg}"),
             Self::MissingRequiredTag(tag) => write!(f, "Missing required tag: {tag}"),
+            Self::InvalidPermission(msg) => write!(f, "Invalid permission value: {msg}"),
             Self::InvalidGroupIdentifier(msg) => {
                 write!(f, "Invalid group identifier format: {msg}")
             }
+            Self::Bech32(msg) => write!(f, "Bech32: {msg}"),
+            Self::InvalidRelayUrl(msg) => write!(f, "Invalid relay URL: {msg}"),
+            Self::WrongKind(kind) => write!(f, "Unexpected event kind: {kind}"),
+            Self::InvalidMetadata(msg) => write!(f, "Invalid group metadata: {msg}"),
+            Self::InvalidPublicKey(msg) => write!(f, "Invalid public key: {msg}"),
+            Self::InvalidRole(msg) => write!(f, "Invalid role: {msg}"),
+            Self::UndefinedRole(role) => write!(f, "Undefined role: {role}"),
+            Self::AdminNotMember(public_key) => {
+                write!(f, "Admin is not a group member: {public_key}")
+            }
+            Self::NotMember(public_key) => write!(f, "Not a group member: {public_key}"),
+            Self::UnprotectedEvent(id) => {
+                write!(f, "Event of a private group is not protected: {id}")
+            }
+            Self::GroupMismatch { expected, found } => {
+                write!(f, "Group mismatch: expected={expected}, found={found}")
+            }
+            Self::CrossGroupReference(reference) => {
+                write!(f, "Reference to an event of another group: {reference}")
+            }
+            Self::TooManyPreviousRefs { count, max } => {
+                write!(f, "Too many timeline references: {count} (max {max})")
+            }
+            Self::EmptyContent => f.write_str("Empty content"),
+            Self::InvalidInviteCode(code) => write!(f, "Invalid invite code: {code}"),
+            Self::InviteCodeMismatch => f.write_str("Invite code doesn't match"),
+            Self::UnexpectedAuthor { expected, found } => {
+                write!(f, "Unexpected author: expected={expected}, found={found}")
+            }
+            Self::MissingPermission {
+                public_key,
+                permission,
+            } => write!(f, "{public_key} lacks the '{permission}' permission"),
         }
     }
 }
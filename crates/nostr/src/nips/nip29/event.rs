@@ -0,0 +1,829 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-29: Events
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::constants::OWNER_MARKER;
+use super::state::put_user_assignments;
+use super::{lowercase_tag_keys, Error, GroupId, GroupTagStyle};
+use crate::nips::nip10::Marker;
+use crate::{Event, EventId, Kind, PublicKey, Tag, TagKind, TagStandard, Timestamp, Url};
+
+/// NIP-29 event kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GroupEventKind {
+    /// Add/update user (kind 9000)
+    PutUser,
+    /// Remove user (kind 9001)
+    RemoveUser,
+    /// Edit metadata (kind 9002)
+    EditMetadata,
+    /// Delete event (kind 9005)
+    DeleteEvent,
+    /// Create group (kind 9007)
+    CreateGroup,
+    /// Delete group (kind 9008)
+    DeleteGroup,
+    /// Create invite (kind 9009)
+    CreateInvite,
+    /// Join request (kind 9021)
+    JoinRequest,
+    /// Leave request (kind 9022)
+    LeaveRequest,
+    /// Group metadata (kind 39000)
+    Metadata,
+    /// Group admins (kind 39001)
+    Admins,
+    /// Group members (kind 39002)
+    Members,
+    /// Group roles (kind 39003)
+    Roles,
+}
+
+impl GroupEventKind {
+    /// Get the NIP-29 event kind of a [`Kind`]
+    ///
+    /// Returns `None` if not a NIP-29 kind.
+    pub fn from_kind(kind: Kind) -> Option<Self> {
+        match kind {
+            Kind::GroupPutUser => Some(Self::PutUser),
+            Kind::GroupRemoveUser => Some(Self::RemoveUser),
+            Kind::GroupEditMetadata => Some(Self::EditMetadata),
+            Kind::GroupDeleteEvent => Some(Self::DeleteEvent),
+            Kind::GroupCreate => Some(Self::CreateGroup),
+            Kind::GroupDelete => Some(Self::DeleteGroup),
+            Kind::GroupCreateInvite => Some(Self::CreateInvite),
+            Kind::GroupJoinRequest => Some(Self::JoinRequest),
+            Kind::GroupLeaveRequest => Some(Self::LeaveRequest),
+            Kind::GroupMetadata => Some(Self::Metadata),
+            Kind::GroupAdmins => Some(Self::Admins),
+            Kind::GroupMembers => Some(Self::Members),
+            Kind::GroupRoles => Some(Self::Roles),
+            _ => None,
+        }
+    }
+
+    /// Get [`Kind`]
+    pub fn to_kind(&self) -> Kind {
+        match self {
+            Self::PutUser => Kind::GroupPutUser,
+            Self::RemoveUser => Kind::GroupRemoveUser,
+            Self::EditMetadata => Kind::GroupEditMetadata,
+            Self::DeleteEvent => Kind::GroupDeleteEvent,
+            Self::CreateGroup => Kind::GroupCreate,
+            Self::DeleteGroup => Kind::GroupDelete,
+            Self::CreateInvite => Kind::GroupCreateInvite,
+            Self::JoinRequest => Kind::GroupJoinRequest,
+            Self::LeaveRequest => Kind::GroupLeaveRequest,
+            Self::Metadata => Kind::GroupMetadata,
+            Self::Admins => Kind::GroupAdmins,
+            Self::Members => Kind::GroupMembers,
+            Self::Roles => Kind::GroupRoles,
+        }
+    }
+}
+
+/// Parsed group message (kind 9)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupMessage {
+    /// Group identifier (`h` or `g` tag value)
+    pub group: String,
+    /// Content
+    pub content: String,
+    previous: Vec<String>,
+    reply: Option<EventId>,
+    quote: Option<EventId>,
+    subject: Option<String>,
+}
+
+impl GroupMessage {
+    /// Timeline references (`previous` tag)
+    ///
+    /// NIP-29 references contain only the first 8 hex characters of the event IDs,
+    /// so they can't be converted to [`EventId`]s.
+    #[inline]
+    pub fn previous(&self) -> &[String] {
+        &self.previous
+    }
+
+    /// Replied event (`e` tag with `reply` marker)
+    #[inline]
+    pub fn reply(&self) -> Option<EventId> {
+        self.reply
+    }
+
+    /// Quoted event (`q` tag)
+    #[inline]
+    pub fn quote(&self) -> Option<EventId> {
+        self.quote
+    }
+
+    /// Subject line (`subject` tag)
+    #[inline]
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+}
+
+impl GroupMessage {
+    /// Parse a group message (kind 9)
+    ///
+    /// The group is read from the `h` tag or, if missing, the `g` tag
+    /// (see [`GroupTagStyle::find`]).
+    /// In `lenient` mode the tag keys are lowercased before matching, so an `H` tag is
+    /// accepted as the group tag (see [`lowercase_tag_keys`]).
+    pub fn parse(event: &Event, lenient: bool) -> Result<Self, Error> {
+        if event.kind != Kind::ChatMessage {
+            return Err(Error::WrongKind(event.kind));
+        }
+
+        let tags: Cow<[Tag]> = if lenient {
+            Cow::Owned(lowercase_tag_keys(event.tags.iter()))
+        } else {
+            Cow::Borrowed(event.tags.as_slice())
+        };
+
+        let group: &str = GroupTagStyle::find(tags.iter())
+            .ok_or_else(|| Error::MissingRequiredTag("h".into()))?;
+
+        let mut message: Self = Self {
+            group: group.to_string(),
+            content: event.content.clone(),
+            previous: Vec::new(),
+            reply: None,
+            quote: None,
+            subject: None,
+        };
+
+        for tag in tags.iter() {
+            match tag.as_standardized() {
+                Some(TagStandard::Previous(refs)) => message.previous.extend(refs.iter().cloned()),
+                Some(TagStandard::Event {
+                    event_id,
+                    marker: Some(Marker::Reply),
+                    ..
+                }) => message.reply = Some(*event_id),
+                Some(TagStandard::Quote { event_id, .. }) => message.quote = Some(*event_id),
+                Some(TagStandard::Subject(subject)) => message.subject = Some(subject.clone()),
+                _ => {}
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+impl TryFrom<&Event> for GroupMessage {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        Self::parse(event, false)
+    }
+}
+
+/// Parsed group poll (kind 9 with `poll_option` tags)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupPoll {
+    /// Group identifier (`h` tag value)
+    pub group: String,
+    /// Question
+    pub question: String,
+    /// Options, sorted by index
+    pub options: Vec<String>,
+}
+
+/// Parse a group poll
+///
+/// Returns an error if the event isn't a group message or if it has no `poll_option` tags.
+/// Options with an invalid index are skipped.
+pub fn parse_poll(event: &Event) -> Result<GroupPoll, Error> {
+    let message: GroupMessage = GroupMessage::try_from(event)?;
+
+    let mut options: Vec<(u32, String)> = event
+        .tags
+        .filter(TagKind::Custom(Cow::Borrowed("poll_option")))
+        .filter_map(|tag| match tag.as_slice() {
+            [_, index, option, ..] => Some((index.parse().ok()?, option.clone())),
+            _ => None,
+        })
+        .collect();
+
+    if options.is_empty() {
+        return Err(Error::MissingRequiredTag("poll_option".into()));
+    }
+
+    options.sort_by_key(|(index, ..)| *index);
+
+    Ok(GroupPoll {
+        group: message.group,
+        question: message.content,
+        options: options.into_iter().map(|(_, option)| option).collect(),
+    })
+}
+
+/// Parsed group invite (kind 9009)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupInvite {
+    /// Invite code (`code` tag)
+    pub code: Option<String>,
+    /// Expiration (`expiration` tag)
+    pub expiration: Option<Timestamp>,
+}
+
+impl GroupInvite {
+    /// Parse an invite event (kind 9009)
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::GroupCreateInvite {
+            return Err(Error::WrongKind(event.kind));
+        }
+
+        Ok(Self {
+            code: event
+                .tags
+                .find(TagKind::Custom(Cow::Borrowed("code")))
+                .and_then(|t| t.content())
+                .map(|code| code.to_string()),
+            expiration: event.tags.expiration().copied(),
+        })
+    }
+}
+
+/// Build the [`GroupId`] of an event received from a relay
+///
+/// Group tags don't always include the relay, so the URL of the relay the event was received
+/// from is attached to the group ID read from the `h` or `g` tag (or the `d` tag, for addressable
+/// events).
+pub fn attach_relay(event: &Event, relay_url: &Url) -> Result<GroupId, Error> {
+    let id: &str = match GroupTagStyle::find(event.tags.iter()) {
+        Some(id) => id,
+        None if event.kind.is_group_metadata() => event
+            .tags
+            .identifier()
+            .ok_or_else(|| Error::MissingRequiredTag("d".into()))?,
+        None => return Err(Error::MissingRequiredTag("h".into())),
+    };
+
+    GroupId::new(relay_url.clone(), id.to_string())
+}
+
+/// Group NIP-29 events by kind, for batch processing
+///
+/// Non-NIP-29 events (see [`Kind::is_group_event`]) are excluded, except for
+/// chat messages (kind 9) with a group tag (`h` or `g`).
+pub fn partition_by_kind(events: &[Event]) -> BTreeMap<Kind, Vec<&Event>> {
+    let mut partitions: BTreeMap<Kind, Vec<&Event>> = BTreeMap::new();
+
+    for event in events.iter().filter(|e| is_partitioned(e)) {
+        partitions.entry(event.kind).or_default().push(event);
+    }
+
+    partitions
+}
+
+fn is_partitioned(event: &Event) -> bool {
+    event.kind.is_group_event()
+        || (event.kind == Kind::ChatMessage && GroupTagStyle::find(event.tags.iter()).is_some())
+}
+
+/// Summarize moderation events for a moderation log
+///
+/// Returns the time, the action and the target public key (if any) of each moderation event,
+/// sorted by timestamp (oldest first). Other events are skipped.
+///
+/// The group owner `p` tag (with the `owner` marker) carried by metadata events is not a target.
+pub fn moderation_log(events: &[Event]) -> Vec<(Timestamp, GroupEventKind, Option<PublicKey>)> {
+    let mut log: Vec<(Timestamp, GroupEventKind, Option<PublicKey>)> = events
+        .iter()
+        .filter(|e| e.kind.is_group_moderation())
+        .filter_map(|e| {
+            let kind: GroupEventKind = GroupEventKind::from_kind(e.kind)?;
+            Some((e.created_at, kind, moderation_target(e)))
+        })
+        .collect();
+    log.sort_by_key(|(created_at, ..)| *created_at);
+    log
+}
+
+fn moderation_target(event: &Event) -> Option<PublicKey> {
+    event
+        .tags
+        .iter()
+        .filter(|t| t.kind() == TagKind::p())
+        .filter(|t| t.as_slice().get(3).map(|m| m.as_str()) != Some(OWNER_MARKER))
+        .find_map(|t| PublicKey::from_hex(t.content()?).ok())
+}
+
+/// Get the reason of a moderation event
+///
+/// The reason is the `reason` tag or, if missing, the content of the event.
+/// Returns `None` if the event is not a moderation event or if the reason is empty.
+pub fn moderation_reason(event: &Event) -> Option<&str> {
+    if !event.kind.is_group_moderation() {
+        return None;
+    }
+
+    let reason: &str = event
+        .tags
+        .find(TagKind::Custom(Cow::Borrowed("reason")))
+        .and_then(|t| t.content())
+        .unwrap_or(&event.content);
+
+    if reason.trim().is_empty() {
+        return None;
+    }
+
+    Some(reason)
+}
+
+/// Abbreviate a public key for display (first 8 hex chars)
+fn abbreviate(public_key: &PublicKey) -> String {
+    let mut hex: String = public_key.to_hex();
+    hex.truncate(8);
+    hex
+}
+
+/// Summarize a group event in a short sentence (e.g. `"<alice> added <bob> as moderator"`)
+///
+/// Public keys are abbreviated to their first 8 hex chars. Useful for notifications.
+///
+/// Returns `None` for non-NIP-29 events.
+pub fn summarize_event(event: &Event) -> Option<String> {
+    let kind: GroupEventKind = GroupEventKind::from_kind(event.kind)?;
+    let author: String = abbreviate(&event.pubkey);
+    let targets: String = event
+        .tags
+        .public_keys()
+        .map(abbreviate)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let summary: String = match kind {
+        GroupEventKind::PutUser => {
+            let users: Vec<String> = put_user_assignments(event)
+                .into_iter()
+                .map(|(public_key, roles)| {
+                    if roles.is_empty() {
+                        abbreviate(&public_key)
+                    } else {
+                        format!("{} as {}", abbreviate(&public_key), roles.join(", "))
+                    }
+                })
+                .collect();
+            format!("{author} added {}", users.join(" and "))
+        }
+        GroupEventKind::RemoveUser => format!("{author} removed {targets}"),
+        GroupEventKind::EditMetadata => format!("{author} edited the group metadata"),
+        GroupEventKind::DeleteEvent if revokes_invite(event) => {
+            format!("{author} revoked an invite")
+        }
+        GroupEventKind::DeleteEvent => format!("{author} deleted an event"),
+        GroupEventKind::CreateGroup => format!("{author} created the group"),
+        GroupEventKind::DeleteGroup => format!("{author} deleted the group"),
+        GroupEventKind::CreateInvite => format!("{author} created an invite"),
+        GroupEventKind::JoinRequest => format!("{author} asked to join the group"),
+        GroupEventKind::LeaveRequest => format!("{author} left the group"),
+        GroupEventKind::Metadata => String::from("Group metadata updated"),
+        GroupEventKind::Admins => String::from("Group admins updated"),
+        GroupEventKind::Members => String::from("Group members updated"),
+        GroupEventKind::Roles => String::from("Group roles updated"),
+    };
+
+    Some(summary)
+}
+
+/// Delete-events carrying a `code` tag revoke an invite
+fn revokes_invite(event: &Event) -> bool {
+    event.tags.find(TagKind::Custom("code".into())).is_some()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::nips::nip29::GroupMetadata;
+    use crate::{EventBuilder, Keys, Tag, Url};
+
+    fn group_id() -> GroupId {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        GroupId::new(url, "test-group".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_group_event_kind() {
+        for kind in [
+            9000, 9001, 9002, 9005, 9007, 9008, 9009, 9021, 9022, 39000, 39001, 39002, 39003,
+        ] {
+            let kind = Kind::from(kind);
+            assert_eq!(GroupEventKind::from_kind(kind).unwrap().to_kind(), kind);
+        }
+
+        assert_eq!(GroupEventKind::from_kind(Kind::ChatMessage), None);
+        assert_eq!(GroupEventKind::from_kind(Kind::TextNote), None);
+    }
+
+    #[test]
+    fn test_moderation_log() {
+        let admin = Keys::generate();
+        let user = Keys::generate().public_key();
+
+        let message = EventBuilder::group_message(group_id(), "Spam")
+            .unwrap()
+            .custom_created_at(Timestamp::from_secs(2))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let add = EventBuilder::group_put_user(group_id(), user, Vec::new())
+            .custom_created_at(Timestamp::from_secs(1))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let delete = EventBuilder::group_delete_event(group_id(), message.id)
+            .custom_created_at(Timestamp::from_secs(3))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let remove = EventBuilder::group_remove_user(group_id(), user)
+            .custom_created_at(Timestamp::from_secs(4))
+            .sign_with_keys(&admin)
+            .unwrap();
+
+        let log = moderation_log(&[remove, delete, message, add]);
+        assert_eq!(
+            log,
+            vec![
+                (Timestamp::from_secs(1), GroupEventKind::PutUser, Some(user)),
+                (Timestamp::from_secs(3), GroupEventKind::DeleteEvent, None),
+                (
+                    Timestamp::from_secs(4),
+                    GroupEventKind::RemoveUser,
+                    Some(user)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_moderation_log_skips_owner() {
+        let admin = Keys::generate();
+        let metadata = GroupMetadata {
+            name: Some(String::from("Test")),
+            owner: Some(Keys::generate().public_key()),
+            ..Default::default()
+        };
+
+        let create = EventBuilder::group_create(group_id(), metadata.clone())
+            .custom_created_at(Timestamp::from_secs(1))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let edit = EventBuilder::group_edit_metadata(group_id(), metadata)
+            .custom_created_at(Timestamp::from_secs(2))
+            .sign_with_keys(&admin)
+            .unwrap();
+
+        let log = moderation_log(&[create, edit]);
+        assert_eq!(
+            log,
+            vec![
+                (Timestamp::from_secs(1), GroupEventKind::CreateGroup, None),
+                (Timestamp::from_secs(2), GroupEventKind::EditMetadata, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_message() {
+        let keys = Keys::generate();
+
+        let first = EventBuilder::group_message(group_id(), "First")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let second = EventBuilder::group_message(group_id(), "Second")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let event = EventBuilder::group_message(group_id(), "Reply")
+            .unwrap()
+            .with_previous_events(vec![first.id, second.id])
+            .tag(Tag::from_standardized_without_cell(TagStandard::Event {
+                event_id: second.id,
+                relay_url: None,
+                marker: Some(Marker::Reply),
+                public_key: None,
+                uppercase: false,
+            }))
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let message = GroupMessage::try_from(&event).unwrap();
+        assert_eq!(message.group, group_id().id);
+        assert_eq!(message.content, "Reply");
+        assert_eq!(
+            message.previous(),
+            [
+                first.id.to_hex()[..8].to_string(),
+                second.id.to_hex()[..8].to_string()
+            ]
+        );
+        assert_eq!(message.reply(), Some(second.id));
+        assert_eq!(message.quote(), None);
+
+        // Not a group message
+        let event = EventBuilder::text_note("Hello")
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            GroupMessage::try_from(&event),
+            Err(Error::WrongKind(Kind::TextNote))
+        );
+    }
+
+    #[test]
+    fn test_attach_relay() {
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+
+        // `h` tag
+        let event = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(attach_relay(&event, &relay_url).unwrap(), group_id());
+
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tag(Tag::group_id("test-group"))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(attach_relay(&event, &relay_url).unwrap(), group_id());
+
+        // `d` tag
+        let event = EventBuilder::group_members(group_id(), Default::default())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(attach_relay(&event, &relay_url).unwrap(), group_id());
+
+        // Missing `h` tag
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            attach_relay(&event, &relay_url),
+            Err(Error::MissingRequiredTag("h".into()))
+        );
+    }
+
+    #[test]
+    fn test_group_tag_style_g_parsing() {
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+
+        let event = EventBuilder::group_message_with_style(group_id(), "Hello", GroupTagStyle::G)
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupTagStyle::H.extract(&event), None);
+
+        let message = GroupMessage::parse(&event, false).unwrap();
+        assert_eq!(message.group, "test-group");
+        assert_eq!(
+            GroupId::from_event(&event, relay_url.clone()).unwrap(),
+            group_id()
+        );
+        assert_eq!(attach_relay(&event, &relay_url).unwrap(), group_id());
+
+        let events = [event.clone()];
+        let partitions = partition_by_kind(&events);
+        assert_eq!(partitions[&Kind::ChatMessage], vec![&event]);
+
+        // The `h` tag takes precedence
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tags([GroupTagStyle::G.to_tag(&group_id()), Tag::group_id("other")])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupMessage::parse(&event, false).unwrap().group, "other");
+    }
+
+    #[test]
+    fn test_partition_by_kind() {
+        let keys = Keys::generate();
+        let user = Keys::generate().public_key();
+
+        let add_1 = EventBuilder::group_put_user(group_id(), user, Vec::new())
+            .sign_with_keys(&keys)
+            .unwrap();
+        let add_2 = EventBuilder::group_put_user(group_id(), keys.public_key(), Vec::new())
+            .sign_with_keys(&keys)
+            .unwrap();
+        let join = EventBuilder::group_join_request(group_id(), None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let chat = EventBuilder::new(Kind::ChatMessage, "Not in a group")
+            .sign_with_keys(&keys)
+            .unwrap();
+        let note = EventBuilder::text_note("Hello")
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let events = [
+            add_1.clone(),
+            note,
+            join.clone(),
+            message.clone(),
+            chat,
+            add_2.clone(),
+        ];
+        let partitions = partition_by_kind(&events);
+
+        assert_eq!(partitions.len(), 3);
+        assert_eq!(partitions[&Kind::GroupPutUser], vec![&add_1, &add_2]);
+        assert_eq!(partitions[&Kind::GroupJoinRequest], vec![&join]);
+        assert_eq!(partitions[&Kind::ChatMessage], vec![&message]);
+        assert!(!partitions.contains_key(&Kind::TextNote));
+    }
+
+    #[test]
+    fn test_group_message_subject() {
+        let keys = Keys::generate();
+
+        let event = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .with_subject("Weekly sync")
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let message = GroupMessage::try_from(&event).unwrap();
+        assert_eq!(message.subject(), Some("Weekly sync"));
+        assert_eq!(message.content, "Hello");
+
+        let event = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupMessage::try_from(&event).unwrap().subject(), None);
+    }
+
+    #[test]
+    fn test_group_poll() {
+        let keys = Keys::generate();
+
+        let event = EventBuilder::group_poll(group_id(), "Next meetup?", &["Lisbon", "Berlin"])
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let poll = parse_poll(&event).unwrap();
+        assert_eq!(poll.group, group_id().id);
+        assert_eq!(poll.question, "Next meetup?");
+        assert_eq!(
+            poll.options,
+            vec!["Lisbon".to_string(), "Berlin".to_string()]
+        );
+
+        // Plain message
+        let event = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            parse_poll(&event),
+            Err(Error::MissingRequiredTag("poll_option".into()))
+        );
+
+        // Empty question
+        assert_eq!(
+            EventBuilder::group_poll(group_id(), "", &["Lisbon"]).unwrap_err(),
+            Error::EmptyContent
+        );
+    }
+
+    #[test]
+    fn test_summarize_event() {
+        let admin = Keys::generate();
+        let user = Keys::generate();
+        let admin_short: String = admin.public_key().to_hex()[..8].to_string();
+        let user_short: String = user.public_key().to_hex()[..8].to_string();
+
+        let put_user =
+            EventBuilder::group_put_user(group_id(), user.public_key(), vec!["moderator".into()])
+                .sign_with_keys(&admin)
+                .unwrap();
+        assert_eq!(
+            summarize_event(&put_user).unwrap(),
+            format!("{admin_short} added {user_short} as moderator")
+        );
+
+        let delete = EventBuilder::group_delete_event(group_id(), put_user.id)
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(
+            summarize_event(&delete).unwrap(),
+            format!("{admin_short} deleted an event")
+        );
+
+        let invite = EventBuilder::group_create_invite(group_id())
+            .sign_with_keys(&admin)
+            .unwrap();
+        let revoke = EventBuilder::group_revoke_invite(group_id(), invite.id, "INVITE123")
+            .unwrap()
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(
+            summarize_event(&revoke).unwrap(),
+            format!("{admin_short} revoked an invite")
+        );
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&user)
+            .unwrap();
+        assert_eq!(summarize_event(&message), None);
+    }
+
+    #[test]
+    fn test_moderation_reason() {
+        let admin = Keys::generate();
+        let user = Keys::generate().public_key();
+
+        let remove = EventBuilder::new(Kind::GroupRemoveUser, "Spamming")
+            .tags([Tag::group_id("test-group"), Tag::public_key(user)])
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(moderation_reason(&remove), Some("Spamming"));
+
+        let remove = EventBuilder::group_remove_user(group_id(), user)
+            .with_reason(Some("Off-topic"))
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(moderation_reason(&remove), Some("Off-topic"));
+
+        let remove = EventBuilder::group_remove_user(group_id(), user)
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(moderation_reason(&remove), None);
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(moderation_reason(&message), None);
+    }
+
+    #[test]
+    fn test_group_invite_from_event() {
+        let keys = Keys::generate();
+        let expiration = Timestamp::from_secs(1_700_000_000);
+
+        let event =
+            EventBuilder::group_create_invite_with_code(group_id(), "abc", Some(expiration))
+                .unwrap()
+                .sign_with_keys(&keys)
+                .unwrap();
+        let invite = GroupInvite::from_event(&event).unwrap();
+        assert_eq!(invite.code.as_deref(), Some("abc"));
+        assert_eq!(invite.expiration, Some(expiration));
+
+        let event = EventBuilder::group_create_invite(group_id())
+            .sign_with_keys(&keys)
+            .unwrap();
+        let invite = GroupInvite::from_event(&event).unwrap();
+        assert_eq!(invite.code, None);
+        assert_eq!(invite.expiration, None);
+
+        let event = EventBuilder::group_join_request(group_id(), None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            GroupInvite::from_event(&event),
+            Err(Error::WrongKind(Kind::GroupJoinRequest))
+        );
+    }
+
+    #[test]
+    fn test_group_message_parse_lenient() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tags([
+                Tag::parse(["H", "test-group"]).unwrap(),
+                Tag::parse(["Subject", "Greetings"]).unwrap(),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(
+            GroupMessage::parse(&event, false),
+            Err(Error::MissingRequiredTag("h".into()))
+        );
+
+        let message = GroupMessage::parse(&event, true).unwrap();
+        assert_eq!(message.group, "test-group");
+        assert_eq!(message.subject(), Some("Greetings"));
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-29: Flows
+//!
+//! Helpers returning all the events needed to perform a group operation.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Error, GroupId, GroupMetadata};
+use crate::{Event, EventBuilder, Kind, Tag, TagKind};
+
+/// Join an open group
+///
+/// Returns the join request (kind 9021) and, if `intro_message` is provided, a first group
+/// message (kind 9). Join requests to open groups are approved automatically,
+/// so the message can be published right after the request.
+///
+/// Returns [`Error::EmptyContent`] if `intro_message` is empty or whitespace-only.
+pub fn join_open_group<S>(
+    group_id: GroupId,
+    intro_message: Option<S>,
+) -> Result<Vec<EventBuilder>, Error>
+where
+    S: Into<String>,
+{
+    let mut builders: Vec<EventBuilder> = vec![EventBuilder::group_join_request(
+        group_id.clone(),
+        None::<String>,
+    )];
+
+    if let Some(message) = intro_message {
+        builders.push(EventBuilder::group_message(group_id, message)?);
+    }
+
+    Ok(builders)
+}
+
+/// Approve a join request (kind 9021)
+///
+/// Returns a put-user event (kind 9000) adding the author of the request to its group
+/// with the given `roles`. The join request is referenced with an `e` tag.
+///
+/// The `h` tag of the request is copied as is, since it doesn't carry the relay URL.
+pub fn approve_join(join_event: &Event, roles: Vec<String>) -> Result<EventBuilder, Error> {
+    if join_event.kind != Kind::GroupJoinRequest {
+        return Err(Error::WrongKind(join_event.kind));
+    }
+
+    let group: &str = join_event
+        .tags
+        .find(TagKind::h())
+        .and_then(|t| t.content())
+        .ok_or_else(|| Error::MissingRequiredTag("h".into()))?;
+
+    Ok(EventBuilder::new(Kind::GroupPutUser, "")
+        .tags([Tag::group_id(group), Tag::public_key(join_event.pubkey)])
+        .tags(roles.into_iter().map(|role| Tag::role(role, None)))
+        .tag(Tag::event(join_event.id)))
+}
+
+/// Migrate a group to a new identifier
+///
+/// Returns, in publishing order:
+/// 1. the creation of the new group (kind 9007);
+/// 2. the metadata of the new group (kind 9002);
+/// 3. a message in the old group pointing to the new one (kind 9);
+/// 4. the deletion of the old group (kind 9008).
+pub fn migrate_group(old: &GroupId, new: &GroupId, metadata: GroupMetadata) -> Vec<EventBuilder> {
+    vec![
+        EventBuilder::group_create(new.clone(), metadata.clone()),
+        EventBuilder::group_edit_metadata(new.clone(), metadata),
+        EventBuilder::group_chat_message(old.clone(), format!("This group moved to {new}")),
+        EventBuilder::group_delete(old.clone()),
+    ]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Keys, Url};
+
+    fn group_id() -> GroupId {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        GroupId::new(url, "test-group".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_join_open_group() {
+        let keys = Keys::generate();
+
+        let kinds: Vec<Kind> = join_open_group(group_id(), Some("Hi everyone!"))
+            .unwrap()
+            .into_iter()
+            .map(|b| b.sign_with_keys(&keys).unwrap().kind)
+            .collect();
+        assert_eq!(kinds, vec![Kind::GroupJoinRequest, Kind::ChatMessage]);
+
+        let kinds: Vec<Kind> = join_open_group(group_id(), None::<String>)
+            .unwrap()
+            .into_iter()
+            .map(|b| b.sign_with_keys(&keys).unwrap().kind)
+            .collect();
+        assert_eq!(kinds, vec![Kind::GroupJoinRequest]);
+
+        assert_eq!(
+            join_open_group(group_id(), Some(" ")).unwrap_err(),
+            Error::EmptyContent
+        );
+    }
+
+    #[test]
+    fn test_migrate_group() {
+        let keys = Keys::generate();
+        let old = group_id();
+        let new = GroupId::new(old.relay_url.clone(), "new-group".to_string()).unwrap();
+
+        let events: Vec<Event> = migrate_group(&old, &new, GroupMetadata::default())
+            .into_iter()
+            .map(|b| b.sign_with_keys(&keys).unwrap())
+            .collect();
+
+        let kinds: Vec<Kind> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::GroupCreate,
+                Kind::GroupEditMetadata,
+                Kind::ChatMessage,
+                Kind::GroupDelete
+            ]
+        );
+
+        // The pointer message is published in the old group and references the new one
+        let pointer = &events[2];
+        assert!(pointer.content.contains(&new.to_string()));
+        assert_eq!(
+            pointer.tags.find(TagKind::h()).and_then(|t| t.content()),
+            Some(old.id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_approve_join() {
+        let admin = Keys::generate();
+        let user = Keys::generate();
+
+        let join = EventBuilder::group_join_request(group_id(), Some("Hi!"))
+            .sign_with_keys(&user)
+            .unwrap();
+
+        let approval = approve_join(&join, vec!["moderator".into()])
+            .unwrap()
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert_eq!(approval.kind, Kind::GroupPutUser);
+        assert_eq!(
+            approval.tags.find(TagKind::h()).and_then(|t| t.content()),
+            join.tags.find(TagKind::h()).and_then(|t| t.content())
+        );
+        assert_eq!(
+            approval.tags.public_keys().copied().collect::<Vec<_>>(),
+            vec![user.public_key()]
+        );
+        assert_eq!(
+            approval.tags.event_ids().copied().collect::<Vec<_>>(),
+            vec![join.id]
+        );
+        assert_eq!(
+            crate::nips::nip29::resolve_user_roles(&user.public_key(), &[approval]),
+            vec!["moderator".to_string()]
+        );
+
+        // Not a join request
+        let leave = EventBuilder::group_leave_request(group_id())
+            .sign_with_keys(&user)
+            .unwrap();
+        assert!(matches!(
+            approve_join(&leave, Vec::new()),
+            Err(Error::WrongKind(Kind::GroupLeaveRequest))
+        ));
+    }
+}
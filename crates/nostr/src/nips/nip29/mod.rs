@@ -58,16 +58,26 @@
 //!     .sign_with_keys(&keys)?;
 //!
 //! // Send a group message
-//! let msg = EventBuilder::group_message(group_id, "Hello everyone!")
+//! let msg = EventBuilder::group_message(group_id, "Hello everyone!")?
 //!     .sign_with_keys(&keys)?;
 //! # Ok(())
 //! # }
 //! ```
 
 pub mod constants;
+pub mod context;
 pub mod error;
+pub mod event;
+pub mod flow;
+pub mod state;
 pub mod types;
+pub mod validation;
 
 pub use self::constants::*;
+pub use self::context::*;
 pub use self::error::Error;
+pub use self::event::*;
+pub use self::flow::*;
+pub use self::state::*;
 pub use self::types::*;
+pub use self::validation::*;
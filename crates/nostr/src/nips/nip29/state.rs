@@ -0,0 +1,540 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-29: State
+//!
+//! Derive group state by replaying moderation events.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use super::validation::addressable_identifier;
+#[cfg(feature = "std")]
+use super::GroupId;
+use super::{Error, GroupAdmins, GroupMembers, GroupMetadata, GroupRoles};
+#[cfg(feature = "std")]
+use crate::event::builder::Error as BuilderError;
+use crate::{Event, Kind, PublicKey, TagStandard, Timestamp};
+#[cfg(feature = "std")]
+use crate::{EventBuilder, Keys};
+
+/// Sort events by `created_at` (oldest first)
+fn chronological(events: &[Event]) -> Vec<&Event> {
+    let mut events: Vec<&Event> = events.iter().collect();
+    events.sort_by_key(|e| e.created_at);
+    events
+}
+
+/// Extract the `(public key, roles)` assignments of a put-user event (kind 9000)
+///
+/// Each `role` tag is assigned to the closest preceding `p` tag.
+pub(crate) fn put_user_assignments(event: &Event) -> Vec<(PublicKey, Vec<String>)> {
+    let mut assignments: Vec<(PublicKey, Vec<String>)> = Vec::new();
+
+    for tag in event.tags.iter() {
+        match tag.as_standardized() {
+            Some(TagStandard::PublicKey { public_key, .. }) => {
+                assignments.push((*public_key, Vec::new()));
+            }
+            Some(TagStandard::Role { name, .. }) => {
+                if let Some((_, roles)) = assignments.last_mut() {
+                    roles.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    assignments
+}
+
+/// Compute the members of a group from put-user (kind 9000) and remove-user (kind 9001) events
+///
+/// Events are applied in timestamp order. Other kinds are ignored.
+pub fn reduce_membership(events: &[Event]) -> GroupMembers {
+    let mut members: Vec<PublicKey> = Vec::new();
+
+    for event in chronological(events) {
+        match event.kind {
+            Kind::GroupPutUser => {
+                for public_key in event.tags.public_keys() {
+                    if !members.contains(public_key) {
+                        members.push(*public_key);
+                    }
+                }
+            }
+            Kind::GroupRemoveUser => {
+                for public_key in event.tags.public_keys() {
+                    members.retain(|m| m != public_key);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    GroupMembers { members }
+}
+
+/// Compute the current roles of a user from put-user (kind 9000) and remove-user (kind 9001) events
+///
+/// Events are applied in timestamp order: each put-user event replaces the roles of the user,
+/// while a remove-user event clears them. Other kinds are ignored.
+pub fn resolve_user_roles(public_key: &PublicKey, events: &[Event]) -> Vec<String> {
+    let mut roles: Vec<String> = Vec::new();
+
+    for event in chronological(events) {
+        match event.kind {
+            Kind::GroupPutUser => {
+                for (user, user_roles) in put_user_assignments(event) {
+                    if &user == public_key {
+                        roles = user_roles;
+                    }
+                }
+            }
+            Kind::GroupRemoveUser => {
+                if event.tags.public_keys().any(|pk| pk == public_key) {
+                    roles.clear();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    roles
+}
+
+/// Compute the current roles of all members from put-user (kind 9000) and remove-user
+/// (kind 9001) events
+///
+/// Same rules as [`resolve_user_roles`]. Removed users are not included, while members
+/// without roles map to an empty list.
+pub fn member_roles_map(events: &[Event]) -> BTreeMap<PublicKey, Vec<String>> {
+    let mut members: BTreeMap<PublicKey, Vec<String>> = BTreeMap::new();
+
+    for event in chronological(events) {
+        match event.kind {
+            Kind::GroupPutUser => members.extend(put_user_assignments(event)),
+            Kind::GroupRemoveUser => {
+                for public_key in event.tags.public_keys() {
+                    members.remove(public_key);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    members
+}
+
+/// Merge members events (kind 39002) split in multiple chunks
+///
+/// All the chunks must have the same `d` tag.
+/// Public keys are de-duplicated, preserving the order of the first occurrence.
+pub fn merge_member_chunks(events: &[Event]) -> Result<GroupMembers, Error> {
+    let mut group: Option<String> = None;
+    let mut members: GroupMembers = GroupMembers::new();
+
+    for event in events.iter() {
+        let chunk: GroupMembers = GroupMembers::from_event(event)?;
+        let id: String = addressable_identifier(event)?;
+
+        match &group {
+            Some(expected) if expected != &id => {
+                return Err(Error::GroupMismatch {
+                    expected: expected.clone(),
+                    found: id,
+                });
+            }
+            Some(..) => {}
+            None => group = Some(id),
+        }
+
+        for public_key in chunk.members.into_iter() {
+            if !members.contains(&public_key) {
+                members.members.push(public_key);
+            }
+        }
+    }
+
+    Ok(members)
+}
+
+/// Collect the authors of the group messages (kind 9) newer than `since`
+///
+/// Useful for "who's online" lists. Authors are de-duplicated, preserving the order
+/// of their first message in `events`.
+pub fn active_members(events: &[Event], since: Timestamp) -> Vec<PublicKey> {
+    let mut authors: Vec<PublicKey> = Vec::new();
+
+    for event in events
+        .iter()
+        .filter(|e| e.kind == Kind::ChatMessage && e.created_at > since)
+    {
+        if !authors.contains(&event.pubkey) {
+            authors.push(event.pubkey);
+        }
+    }
+
+    authors
+}
+
+/// Check if a group has no members and no admins
+///
+/// Useful to prompt the cleanup of abandoned groups.
+#[inline]
+pub fn is_empty_group(members: &GroupMembers, admins: &GroupAdmins) -> bool {
+    members.members.is_empty() && admins.admins.is_empty()
+}
+
+/// Pick the authoritative group metadata event (kind 39000) among conflicting ones
+///
+/// When multiple authors published the metadata of the same group, the events of
+/// `expected_author` (i.e. the relay key) are preferred. Among the candidates, the newest
+/// event wins; on timestamp ties, the lowest event ID wins (as for replaceable events).
+///
+/// Other kinds are ignored.
+pub fn authoritative_metadata(
+    events: &[Event],
+    expected_author: Option<PublicKey>,
+) -> Option<&Event> {
+    let metadata = || events.iter().filter(|e| e.kind == Kind::GroupMetadata);
+
+    let by_expected_author: Vec<&Event> = match expected_author {
+        Some(author) => metadata().filter(|e| e.pubkey == author).collect(),
+        None => Vec::new(),
+    };
+
+    let candidates: Vec<&Event> = if by_expected_author.is_empty() {
+        metadata().collect()
+    } else {
+        by_expected_author
+    };
+
+    candidates.into_iter().max_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| b.id.cmp(&a.id))
+    })
+}
+
+/// Snapshot of the whole state of a group
+///
+/// Serializable to JSON, so it can be used as a portable backup format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupStateDoc {
+    /// Metadata (kind 39000)
+    pub metadata: GroupMetadata,
+    /// Admins (kind 39001)
+    pub admins: GroupAdmins,
+    /// Members (kind 39002)
+    pub members: GroupMembers,
+    /// Roles (kind 39003)
+    pub roles: GroupRoles,
+}
+
+impl GroupStateDoc {
+    /// Collect the group state from its addressable events (kinds 39000-39003)
+    ///
+    /// The newest event of each kind is used: missing kinds fall back to their defaults.
+    /// Other kinds are ignored.
+    pub fn from_events(events: &[Event]) -> Result<Self, Error> {
+        let mut doc: Self = Self::default();
+        let mut newest: Vec<&Event> = Vec::new();
+
+        for event in chronological(events)
+            .into_iter()
+            .filter(|e| e.kind.is_group_metadata())
+        {
+            newest.retain(|e| e.kind != event.kind);
+            newest.push(event);
+        }
+
+        for event in newest.into_iter() {
+            match event.kind {
+                Kind::GroupMetadata => doc.metadata = GroupMetadata::from_tags(event.tags.iter())?,
+                Kind::GroupAdmins => doc.admins = GroupAdmins::from_tags(event.tags.iter()),
+                Kind::GroupMembers => doc.members = GroupMembers::from_tags(event.tags.iter()),
+                Kind::GroupRoles => doc.roles = GroupRoles::from_tags(event.tags.iter())?,
+                _ => {}
+            }
+        }
+
+        Ok(doc)
+    }
+
+    /// Regenerate the addressable events of the group (kinds 39000-39003)
+    #[cfg(feature = "std")]
+    pub fn to_events(&self, group_id: GroupId, keys: &Keys) -> Result<Vec<Event>, BuilderError> {
+        Ok(vec![
+            EventBuilder::group_metadata(group_id.clone(), self.metadata.clone())
+                .sign_with_keys(keys)?,
+            EventBuilder::group_admins(group_id.clone(), self.admins.clone())
+                .sign_with_keys(keys)?,
+            EventBuilder::group_members(group_id.clone(), self.members.clone())
+                .sign_with_keys(keys)?,
+            EventBuilder::group_roles(group_id, self.roles.clone()).sign_with_keys(keys)?,
+        ])
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::nips::nip29::{GroupAdmin, Privacy, Role};
+    use crate::Url;
+
+    fn group_id() -> GroupId {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        GroupId::new(url, "test-group".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_reduce_membership() {
+        let admin = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+
+        let add_alice = EventBuilder::group_put_user(group_id(), alice, Vec::new())
+            .custom_created_at(Timestamp::from_secs(1))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let add_bob = EventBuilder::group_put_user(group_id(), bob, Vec::new())
+            .custom_created_at(Timestamp::from_secs(2))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let remove_alice = EventBuilder::group_remove_user(group_id(), alice)
+            .custom_created_at(Timestamp::from_secs(3))
+            .sign_with_keys(&admin)
+            .unwrap();
+
+        // Input order must not matter
+        let members = reduce_membership(&[remove_alice, add_bob, add_alice]);
+        assert_eq!(members.members, vec![bob]);
+    }
+
+    #[test]
+    fn test_resolve_user_roles() {
+        let admin = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+
+        let promote = EventBuilder::group_put_user(group_id(), alice, vec!["moderator".into()])
+            .custom_created_at(Timestamp::from_secs(1))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let add_bob = EventBuilder::group_put_user(group_id(), bob, vec!["admin".into()])
+            .custom_created_at(Timestamp::from_secs(2))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let events = vec![add_bob, promote];
+        assert_eq!(
+            resolve_user_roles(&alice, &events),
+            vec!["moderator".to_string()]
+        );
+
+        let remove = EventBuilder::group_remove_user(group_id(), alice)
+            .custom_created_at(Timestamp::from_secs(3))
+            .sign_with_keys(&admin)
+            .unwrap();
+        let events = [events, vec![remove]].concat();
+        assert!(resolve_user_roles(&alice, &events).is_empty());
+        assert_eq!(resolve_user_roles(&bob, &events), vec!["admin".to_string()]);
+    }
+
+    #[test]
+    fn test_group_state_doc() {
+        let relay = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+
+        let doc = GroupStateDoc {
+            metadata: GroupMetadata {
+                name: Some("Rust devs".to_string()),
+                privacy: Privacy::Private,
+                ..Default::default()
+            },
+            admins: GroupAdmins::new().add_admin(GroupAdmin::new(
+                alice,
+                vec!["admin".into(), "moderator".into()],
+            )),
+            members: GroupMembers::new().add_member(alice).add_member(bob),
+            roles: GroupRoles::new()
+                .add_role(Role::with_description("admin", "Full access"))
+                .add_role(Role::new("moderator")),
+        };
+
+        let events = doc.to_events(group_id(), &relay).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(GroupStateDoc::from_events(&events).unwrap(), doc);
+
+        let json: String = serde_json::to_string(&doc).unwrap();
+        let parsed: GroupStateDoc = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, doc);
+
+        let events = parsed.to_events(group_id(), &relay).unwrap();
+        assert_eq!(GroupStateDoc::from_events(&events).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_is_empty_group() {
+        let public_key = Keys::generate().public_key();
+
+        assert!(is_empty_group(&GroupMembers::new(), &GroupAdmins::new()));
+
+        let members = GroupMembers::new().add_member(public_key);
+        assert!(!is_empty_group(&members, &GroupAdmins::new()));
+
+        let admins = GroupAdmins::new().add_admin(GroupAdmin::new(public_key, Vec::new()));
+        assert!(!is_empty_group(&GroupMembers::new(), &admins));
+    }
+
+    #[test]
+    fn test_merge_member_chunks() {
+        let relay = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        let carol = Keys::generate().public_key();
+
+        let first = EventBuilder::group_members(
+            group_id(),
+            GroupMembers::new().add_member(alice).add_member(bob),
+        )
+        .sign_with_keys(&relay)
+        .unwrap();
+        let second = EventBuilder::group_members(
+            group_id(),
+            GroupMembers::new().add_member(bob).add_member(carol),
+        )
+        .sign_with_keys(&relay)
+        .unwrap();
+
+        let members = merge_member_chunks(&[first.clone(), second]).unwrap();
+        assert_eq!(members.members, vec![alice, bob, carol]);
+
+        // Chunk of another group
+        let other_group = GroupId::new(group_id().relay_url, "other".to_string()).unwrap();
+        let other = EventBuilder::group_members(other_group, GroupMembers::new().add_member(carol))
+            .sign_with_keys(&relay)
+            .unwrap();
+        assert_eq!(
+            merge_member_chunks(&[first, other]),
+            Err(Error::GroupMismatch {
+                expected: "test-group".into(),
+                found: "other".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_active_members() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let carol = Keys::generate();
+
+        let message = |keys: &Keys, secs: u64| {
+            EventBuilder::group_message(group_id(), "Hello")
+                .unwrap()
+                .custom_created_at(Timestamp::from_secs(secs))
+                .sign_with_keys(keys)
+                .unwrap()
+        };
+
+        let events = vec![
+            message(&carol, 5),
+            message(&alice, 20),
+            message(&bob, 30),
+            message(&alice, 40),
+            EventBuilder::group_leave_request(group_id())
+                .custom_created_at(Timestamp::from_secs(50))
+                .sign_with_keys(&carol)
+                .unwrap(),
+        ];
+
+        assert_eq!(
+            active_members(&events, Timestamp::from_secs(10)),
+            vec![alice.public_key(), bob.public_key()]
+        );
+        assert!(active_members(&events, Timestamp::from_secs(40)).is_empty());
+    }
+
+    #[test]
+    fn test_authoritative_metadata() {
+        let relay = Keys::generate();
+        let other = Keys::generate();
+
+        let metadata = |keys: &Keys, name: &str, created_at: u64| {
+            let metadata = GroupMetadata {
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            EventBuilder::group_metadata(group_id(), metadata)
+                .custom_created_at(Timestamp::from_secs(created_at))
+                .sign_with_keys(keys)
+                .unwrap()
+        };
+
+        let old_relay = metadata(&relay, "Old", 1);
+        let new_relay = metadata(&relay, "New", 2);
+        let spoofed = metadata(&other, "Spoofed", 3);
+        let events = [old_relay, new_relay.clone(), spoofed.clone()];
+
+        // Expected author wins, even if older
+        assert_eq!(
+            authoritative_metadata(&events, Some(relay.public_key())),
+            Some(&new_relay)
+        );
+
+        // Only timestamp tie-breaking
+        assert_eq!(authoritative_metadata(&events, None), Some(&spoofed));
+
+        // No event of the expected author
+        assert_eq!(
+            authoritative_metadata(&events[2..], Some(relay.public_key())),
+            Some(&spoofed)
+        );
+
+        assert_eq!(authoritative_metadata(&[], None), None);
+    }
+
+    #[test]
+    fn test_member_roles_map() {
+        let admin = Keys::generate();
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        let carol = Keys::generate().public_key();
+
+        let add = EventBuilder::group_put_users(
+            group_id(),
+            vec![
+                (alice, vec![String::from("moderator")]),
+                (bob, Vec::new()),
+                (carol, vec![String::from("admin")]),
+            ],
+        )
+        .custom_created_at(Timestamp::from_secs(1))
+        .sign_with_keys(&admin)
+        .unwrap();
+        let promote_bob =
+            EventBuilder::group_put_user(group_id(), bob, vec![String::from("admin")])
+                .custom_created_at(Timestamp::from_secs(2))
+                .sign_with_keys(&admin)
+                .unwrap();
+        let remove_carol = EventBuilder::group_remove_user(group_id(), carol)
+            .custom_created_at(Timestamp::from_secs(3))
+            .sign_with_keys(&admin)
+            .unwrap();
+
+        let members = member_roles_map(&[remove_carol, promote_bob, add]);
+        assert_eq!(
+            members,
+            BTreeMap::from([
+                (alice, vec![String::from("moderator")]),
+                (bob, vec![String::from("admin")]),
+            ])
+        );
+    }
+}
@@ -4,22 +4,38 @@
 
 //! NIP-29: Types
 
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt;
 use core::str::FromStr;
 
+use bech32::{Bech32, Hrp};
+use hashes::sha256::Hash as Sha256Hash;
+use hashes::Hash;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
+
 use crate::event::tag::TagKind;
-use crate::{PublicKey, Tag, Url};
+use crate::nips::nip19::{self, FromBech32, ToBech32};
+use crate::{Alphabet, Event, Filter, Kind, PublicKey, SingleLetterTag, Tag, TagStandard, Url};
 
-use super::constants::TOP_LEVEL_GROUP_ID;
+use super::constants::{
+    MAX_ROLE_NAME_LEN, NIP29_METADATA_KINDS, NIP29_MODERATION_KINDS, NIP29_USER_KINDS,
+    OWNER_MARKER, PREFIX_BECH32_GROUP, TOP_LEVEL_GROUP_ID,
+};
 use super::Error;
 
+const HRP_GROUP: Hrp = Hrp::parse_unchecked(PREFIX_BECH32_GROUP);
+
 /// Group identifier in format: `<relay-url>'<group-id>`
 ///
 /// Group IDs must contain only characters: a-z, 0-9, -, _
 /// The special ID "_" represents a top-level relay-local discussion group.
 ///
+/// Relay hosts are lowercased when the URL is parsed, so group IDs that differ only
+/// in the capitalization of the relay host are equal. The path case is preserved.
+///
 /// # Example
 /// ```rust,no_run
 /// use nostr::nips::nip29::GroupId;
@@ -41,13 +57,38 @@ impl GroupId {
     /// Create a new group identifier
     ///
     /// Returns error if the ID contains invalid characters
+    /// or if the relay URL isn't a `ws://` or `wss://` URL.
     pub fn new(relay_url: Url, id: String) -> Result<Self, Error> {
         Self::validate_id(&id)?;
-        Ok(Self { relay_url, id })
+        let group_id: Self = Self { relay_url, id };
+        group_id.validate_relay_url()?;
+        Ok(group_id)
+    }
+
+    /// Extract the group ID from the `h` tag of an event
+    ///
+    /// The `h` tag doesn't include the relay, so the URL of the relay the event comes from
+    /// must be provided. A `g` tag is accepted too (see [`GroupTagStyle::find`]).
+    pub fn from_event(event: &Event, relay_url: Url) -> Result<Self, Error> {
+        let id: &str = GroupTagStyle::find(event.tags.iter())
+            .ok_or_else(|| Error::MissingRequiredTag("h".into()))?;
+
+        Self::new(relay_url, id.to_string())
+    }
+
+    /// Top-level relay-local discussion group (`_`)
+    ///
+    /// Returns an error if the relay URL isn't a `ws://` or `wss://` URL.
+    #[inline]
+    pub fn top_level(relay_url: Url) -> Result<Self, Error> {
+        Self::new(relay_url, TOP_LEVEL_GROUP_ID.to_string())
     }
 
     /// Validate group ID format
-    fn validate_id(id: &str) -> Result<(), Error> {
+    ///
+    /// The ID must be non-empty and contain only `a-z`, `0-9`, `-` and `_`.
+    /// Useful to validate user input before the relay URL is known.
+    pub fn validate_id(id: &str) -> Result<(), Error> {
         if id.is_empty() {
             return Err(Error::InvalidGroupId("Group ID cannot be empty".into()));
         }
@@ -73,6 +114,69 @@ impl GroupId {
         let url_str = self.relay_url.as_str().trim_end_matches('/');
         format!("{}'{}", url_str, self.id)
     }
+
+    /// Get the relay URL to open the WebSocket connection to
+    ///
+    /// Use [`GroupId::validate_relay_url`] to check that it's usable.
+    #[inline]
+    pub fn relay_ws_url(&self) -> &Url {
+        &self.relay_url
+    }
+
+    /// Validate that the relay URL is usable for a WebSocket connection
+    ///
+    /// The URL must have a host and the `ws` or `wss` scheme.
+    #[inline]
+    pub fn validate_relay_url(&self) -> Result<(), Error> {
+        validate_relay_url(&self.relay_url)
+    }
+
+    /// Get the value to use in `#h` filters
+    ///
+    /// Relays index the `h` tag by the bare group ID, not by the `relay'id` form.
+    #[inline]
+    pub fn filter_tag_value(&self) -> String {
+        self.id.clone()
+    }
+
+    /// Filter for the join requests (kind 9021) of this group
+    ///
+    /// Used by admins to subscribe to the requests awaiting approval.
+    pub fn pending_joins_filter(&self) -> Filter {
+        Filter::new().kind(Kind::GroupJoinRequest).custom_tag(
+            SingleLetterTag::lowercase(Alphabet::H),
+            self.filter_tag_value(),
+        )
+    }
+
+    /// Filter for the events of this group tagged with `h`
+    ///
+    /// Matches group messages (kind 9), moderation events (kinds 9000-9009) and
+    /// join/leave requests (kinds 9021-9022).
+    /// The addressable state events are matched by [`GroupId::metadata_filter`].
+    pub fn subscription_filter(&self) -> Filter {
+        let kinds = NIP29_MODERATION_KINDS
+            .iter()
+            .chain(NIP29_USER_KINDS.iter())
+            .map(|kind| Kind::from(*kind));
+
+        Filter::new()
+            .kind(Kind::ChatMessage)
+            .kinds(kinds)
+            .custom_tag(
+                SingleLetterTag::lowercase(Alphabet::H),
+                self.filter_tag_value(),
+            )
+    }
+
+    /// Filter for the addressable state events of this group (kinds 39000-39003)
+    ///
+    /// Matches the metadata, admins, members and roles by the `d` tag.
+    pub fn metadata_filter(&self) -> Filter {
+        Filter::new()
+            .kinds(NIP29_METADATA_KINDS.iter().map(|kind| Kind::from(*kind)))
+            .identifier(self.id.clone())
+    }
 }
 
 impl fmt::Display for GroupId {
@@ -101,6 +205,202 @@ impl FromStr for GroupId {
     }
 }
 
+impl Serialize for GroupId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_tag_value())
+    }
+}
+
+impl<'de> Deserialize<'de> for GroupId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let group_id: String = String::deserialize(deserializer)?;
+        Self::from_str(&group_id).map_err(serde::de::Error::custom)
+    }
+}
+
+// Shareable `ngroup` bech32 encoding, following the NIP-19 TLV layout:
+// the group ID is the `special` entry and the relay URL the `relay` entry.
+impl ToBech32 for GroupId {
+    type Err = Error;
+
+    fn to_bech32(&self) -> Result<String, Self::Err> {
+        let id: &[u8] = self.id.as_bytes();
+        let relay: &[u8] = self.relay_url.as_str().as_bytes();
+
+        let id_len: u8 = u8::try_from(id.len())
+            .map_err(|_| Error::InvalidGroupId("Group ID too long to encode".into()))?;
+        let relay_len: u8 = u8::try_from(relay.len())
+            .map_err(|_| Error::InvalidRelayUrl("Relay URL too long to encode".into()))?;
+
+        let mut bytes: Vec<u8> = Vec::with_capacity(4 + id.len() + relay.len());
+
+        bytes.push(nip19::SPECIAL); // Type
+        bytes.push(id_len); // Len
+        bytes.extend(id); // Value
+
+        bytes.push(nip19::RELAY); // Type
+        bytes.push(relay_len); // Len
+        bytes.extend(relay); // Value
+
+        bech32::encode::<Bech32>(HRP_GROUP, &bytes).map_err(|e| Error::Bech32(e.to_string()))
+    }
+}
+
+impl FromBech32 for GroupId {
+    type Err = Error;
+
+    fn from_bech32(group: &str) -> Result<Self, Self::Err> {
+        let (hrp, data) = bech32::decode(group).map_err(|e| Error::Bech32(e.to_string()))?;
+
+        if hrp != HRP_GROUP {
+            return Err(Error::Bech32(format!(
+                "Wrong prefix: expected {PREFIX_BECH32_GROUP}"
+            )));
+        }
+
+        let mut id: Option<String> = None;
+        let mut relay_url: Option<Url> = None;
+        let mut data: &[u8] = data.as_slice();
+
+        while !data.is_empty() {
+            let (t, l) = match data {
+                [t, l, ..] => (*t, *l as usize),
+                _ => return Err(Error::Bech32("Invalid TLV".into())),
+            };
+            let bytes: &[u8] = data
+                .get(2..l + 2)
+                .ok_or_else(|| Error::Bech32("Invalid TLV".into()))?;
+            let value: String = String::from_utf8_lossy(bytes).to_string();
+
+            match t {
+                nip19::SPECIAL if id.is_none() => id = Some(value),
+                nip19::RELAY if relay_url.is_none() => {
+                    let url = Url::parse(&value)
+                        .map_err(|e| Error::InvalidRelayUrl(format!("{value}: {e}")))?;
+                    relay_url = Some(url);
+                }
+                _ => {}
+            }
+
+            data = &data[l + 2..];
+        }
+
+        match (relay_url, id) {
+            (Some(relay_url), Some(id)) => Self::new(relay_url, id),
+            (None, _) => Err(Error::MissingRequiredTag("relay".into())),
+            (_, None) => Err(Error::MissingRequiredTag("group id".into())),
+        }
+    }
+}
+
+/// Parse a list of group identifiers (format: `relay'id`)
+///
+/// Each line is trimmed and blank lines are skipped.
+/// Returns the parsed group IDs and the errors paired with the (zero-based) index of their line.
+pub fn parse_group_list(lines: &[&str]) -> (Vec<GroupId>, Vec<(usize, Error)>) {
+    let mut group_ids: Vec<GroupId> = Vec::new();
+    let mut errors: Vec<(usize, Error)> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let line: &str = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        match GroupId::from_str(line) {
+            Ok(group_id) => group_ids.push(group_id),
+            Err(e) => errors.push((index, e)),
+        }
+    }
+
+    (group_ids, errors)
+}
+
+/// Get the relays to publish a group event to
+///
+/// Returns the relay hosting the group followed by its mirror relays (if any),
+/// without duplicates.
+pub fn publish_targets(group_id: &GroupId, metadata: Option<&GroupMetadata>) -> Vec<Url> {
+    let mut targets: Vec<Url> = vec![group_id.relay_url.clone()];
+
+    if let Some(metadata) = metadata {
+        for mirror in metadata.mirrors.iter() {
+            if !targets.contains(mirror) {
+                targets.push(mirror.clone());
+            }
+        }
+    }
+
+    targets
+}
+
+/// Tag key used to carry the group identifier
+///
+/// NIP-29 uses the `h` tag, but some experimental relays use `g` instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GroupTagStyle {
+    /// `h` tag
+    #[default]
+    H,
+    /// `g` tag
+    G,
+}
+
+impl GroupTagStyle {
+    /// Get tag kind
+    #[inline]
+    pub fn tag_kind(&self) -> TagKind<'static> {
+        match self {
+            Self::H => TagKind::h(),
+            Self::G => TagKind::single_letter(Alphabet::G, false),
+        }
+    }
+
+    /// Build the group identifier tag
+    pub fn to_tag(&self, group_id: &GroupId) -> Tag {
+        match self {
+            Self::H => Tag::group_id(group_id.id.clone()),
+            Self::G => Tag::custom(self.tag_kind(), [group_id.id.clone()]),
+        }
+    }
+
+    /// Extract the group identifier tag value from an event
+    pub fn extract<'a>(&self, event: &'a Event) -> Option<&'a str> {
+        event.tags.find(self.tag_kind()).and_then(|t| t.content())
+    }
+
+    /// Find the group identifier in tags of any style
+    ///
+    /// The `h` tag takes precedence: the `g` tag is only used when there's no `h` tag.
+    pub fn find<'a, I>(tags: I) -> Option<&'a str>
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let mut fallback: Option<&str> = None;
+
+        for tag in tags.into_iter() {
+            let kind: TagKind = tag.kind();
+
+            if kind == Self::H.tag_kind() {
+                return tag.content();
+            }
+
+            if fallback.is_none() && kind == Self::G.tag_kind() {
+                fallback = tag.content();
+            }
+        }
+
+        fallback
+    }
+}
+
 /// Group privacy setting
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Privacy {
@@ -119,6 +419,12 @@ impl Privacy {
             Self::Private => "private",
         }
     }
+
+    /// Check if the group can be read by external users
+    #[inline]
+    pub fn is_public(&self) -> bool {
+        matches!(self, Self::Public)
+    }
 }
 
 impl fmt::Display for Privacy {
@@ -142,6 +448,25 @@ impl FromStr for Privacy {
     }
 }
 
+impl Serialize for Privacy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Privacy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value: String = String::deserialize(deserializer)?;
+        Self::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Group access model
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AccessModel {
@@ -160,6 +485,12 @@ impl AccessModel {
             Self::Closed => "closed",
         }
     }
+
+    /// Check if join requests must be approved by an admin
+    #[inline]
+    pub fn requires_approval(&self) -> bool {
+        matches!(self, Self::Closed)
+    }
 }
 
 impl fmt::Display for AccessModel {
@@ -183,330 +514,2722 @@ impl FromStr for AccessModel {
     }
 }
 
+impl Serialize for AccessModel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessModel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value: String = String::deserialize(deserializer)?;
+        Self::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Group metadata
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// When serialized with serde, `None` fields and empty lists are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GroupMetadata {
     /// Display name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub about: Option<String>,
     /// Group image URL
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub picture: Option<Url>,
     /// Privacy setting
     pub privacy: Privacy,
     /// Access model
     pub closed: AccessModel,
+    /// Language (BCP-47 code)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Rules URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Url>,
+    /// Whether the group should be listed in public directories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discoverable: Option<bool>,
+    /// Group owner (`p` tag with `owner` marker)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<PublicKey>,
+    /// Topics, for discovery (`t` tags)
+    ///
+    /// Categories are serialized as hashtags, so they are lowercased.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    /// Relays mirroring the group (`mirror` tags)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<Url>,
+    /// Event kinds that members are allowed to post (`k` tags)
+    ///
+    /// An empty list means that all kinds are allowed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_kinds: Vec<Kind>,
 }
 
-impl From<GroupMetadata> for Vec<Tag> {
-    fn from(metadata: GroupMetadata) -> Self {
-        let mut tags = Vec::new();
-
-        if let Some(name) = metadata.name {
-            tags.push(Tag::custom(TagKind::Name, [name]));
-        }
-
-        if let Some(about) = metadata.about {
-            tags.push(Tag::custom(TagKind::Description, [about]));
+impl GroupMetadata {
+    /// Parse group metadata from tags
+    ///
+    /// Unknown tags are ignored. Missing `privacy` and `closed` tags fall back to their defaults.
+    /// Empty-valued tags (e.g. `["about", ""]`) mark a cleared field and are skipped too,
+    /// so the tags produced by [`GroupMetadata::diff_tags`] can be parsed back.
+    ///
+    /// Some clients emit the group name as a `title` tag: it's used as a fallback when the `name`
+    /// tag is absent. Serialization always uses `name`.
+    ///
+    /// Mirror relay URLs must have a host and the `ws` or `wss` scheme.
+    pub fn from_tags<'a, I>(tags: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let mut metadata = Self::default();
+        let mut title: Option<String> = None;
+
+        for tag in tags.into_iter() {
+            let Some(value) = tag.content().filter(|value| !value.is_empty()) else {
+                continue;
+            };
+
+            match tag.kind() {
+                TagKind::Name => metadata.name = Some(value.to_string()),
+                TagKind::Title => title = Some(value.to_string()),
+                TagKind::Description => metadata.about = Some(value.to_string()),
+                TagKind::Image => metadata.picture = parse_picture(value)?,
+                TagKind::SingleLetter(SingleLetterTag {
+                    character: Alphabet::T,
+                    uppercase: false,
+                }) => metadata.categories.push(value.to_string()),
+                TagKind::SingleLetter(SingleLetterTag {
+                    character: Alphabet::K,
+                    uppercase: false,
+                }) => {
+                    let kind: u16 = value.parse().map_err(|_| {
+                        Error::InvalidMetadata(format!("Invalid allowed kind: {value}"))
+                    })?;
+                    metadata.allowed_kinds.push(Kind::from(kind));
+                }
+                // Only `p` tags with the `owner` marker: other `p` tags (e.g. admins) are ignored
+                TagKind::SingleLetter(SingleLetterTag {
+                    character: Alphabet::P,
+                    uppercase: false,
+                }) if tag.as_slice().get(3).map(|m| m.as_str()) == Some(OWNER_MARKER) => {
+                    let owner = PublicKey::from_hex(value)
+                        .map_err(|e| Error::InvalidPublicKey(format!("owner: {e}")))?;
+                    metadata.owner = Some(owner);
+                }
+                TagKind::Custom(kind) => match kind.as_ref() {
+                    // Spec names of the `description` and `image` tags
+                    "about" => metadata.about = Some(value.to_string()),
+                    "picture" => metadata.picture = parse_picture(value)?,
+                    "privacy" => metadata.privacy = Privacy::from_str(value)?,
+                    "closed" => metadata.closed = AccessModel::from_str(value)?,
+                    "lang" => {
+                        validate_language(value)?;
+                        metadata.language = Some(value.to_string());
+                    }
+                    "rules" => {
+                        let url = Url::parse(value).map_err(|e| {
+                            Error::InvalidMetadata(format!("Invalid rules URL: {e}"))
+                        })?;
+                        metadata.rules = Some(url);
+                    }
+                    "mirror" => {
+                        let url = Url::parse(value).map_err(|e| {
+                            Error::InvalidMetadata(format!("Invalid mirror relay URL: {e}"))
+                        })?;
+                        validate_relay_url(&url)?;
+                        metadata.mirrors.push(url);
+                    }
+                    "discoverable" => {
+                        let discoverable: bool = value.parse().map_err(|_| {
+                            Error::InvalidMetadata(format!(
+                                "Expected 'true' or 'false' for discoverable, got: {value}"
+                            ))
+                        })?;
+                        metadata.discoverable = Some(discoverable);
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
         }
 
-        if let Some(picture) = metadata.picture {
-            tags.push(Tag::custom(TagKind::Image, [picture.to_string()]));
+        if metadata.name.is_none() {
+            metadata.name = title;
         }
 
-        tags.push(Tag::custom(TagKind::Custom("privacy".into()), [metadata.privacy.as_str()]));
-        tags.push(Tag::custom(TagKind::Custom("closed".into()), [metadata.closed.as_str()]));
+        Ok(metadata)
+    }
 
-        tags
+    /// Check if members are allowed to post events of the given kind
+    ///
+    /// Always `true` if the group doesn't restrict the allowed kinds.
+    pub fn allows_kind(&self, kind: Kind) -> bool {
+        self.allowed_kinds.is_empty() || self.allowed_kinds.contains(&kind)
     }
-}
 
-/// Role definition
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Role {
-    /// Role name
-    pub name: String,
-    /// Optional description
-    pub description: Option<String>,
-}
+    /// Compute the tags of the fields changed in `new`, for a minimal edit (kind 9002)
+    ///
+    /// Multi-valued fields (e.g. categories) are emitted in full when any value changed.
+    /// Fields cleared in `new` produce an empty-valued tag (e.g. `["about", ""]`),
+    /// like the empty `image` tag of [`EventBuilder::group_set_picture`], except for
+    /// `privacy` and `closed` which are reset to their default value (`public` and `open`).
+    /// The `h` tag is not included.
+    ///
+    /// [`EventBuilder::group_set_picture`]: crate::EventBuilder::group_set_picture
+    pub fn diff_tags(&self, new: &GroupMetadata) -> Vec<Tag> {
+        let old: Vec<Tag> = self.clone().into();
+        let new: Vec<Tag> = new.clone().into();
+
+        let changed = |kind: &TagKind| {
+            old.iter()
+                .filter(|t| &t.kind() == kind)
+                .ne(new.iter().filter(|t| &t.kind() == kind))
+        };
 
-impl Role {
-    /// Create a new role
-    pub fn new<S>(name: S) -> Self
-    where
-        S: Into<String>,
-    {
-        Self {
-            name: name.into(),
-            description: None,
+        let mut tags: Vec<Tag> = new.iter().filter(|t| changed(&t.kind())).cloned().collect();
+
+        // Cleared fields
+        let defaults: Vec<Tag> = GroupMetadata::default().into();
+        let mut cleared: Vec<TagKind> = Vec::new();
+        for tag in old.iter() {
+            let kind: TagKind = tag.kind();
+            if !cleared.contains(&kind) && !new.iter().any(|t| t.kind() == kind) {
+                let tag: Tag = match defaults.iter().find(|t| t.kind() == kind) {
+                    Some(default) => default.clone(),
+                    None => Tag::custom(kind.clone(), [""]),
+                };
+                tags.push(tag);
+                cleared.push(kind);
+            }
         }
+
+        tags
     }
 
-    /// Create a new role with description
-    pub fn with_description<S, D>(name: S, description: D) -> Self
-    where
-        S: Into<String>,
-        D: Into<String>,
-    {
-        Self {
-            name: name.into(),
-            description: Some(description.into()),
+    /// Compare with another version of the metadata
+    ///
+    /// Each changed field holds its `(old, new)` values. Unchanged fields are `None`.
+    pub fn diff(&self, other: &GroupMetadata) -> GroupMetadataDiff {
+        GroupMetadataDiff {
+            name: field_diff(&self.name, &other.name),
+            about: field_diff(&self.about, &other.about),
+            picture: field_diff(&self.picture, &other.picture),
+            privacy: field_diff(&self.privacy, &other.privacy),
+            closed: field_diff(&self.closed, &other.closed),
+            language: field_diff(&self.language, &other.language),
+            rules: field_diff(&self.rules, &other.rules),
+            discoverable: field_diff(&self.discoverable, &other.discoverable),
+            owner: field_diff(&self.owner, &other.owner),
+            categories: field_diff(&self.categories, &other.categories),
+            mirrors: field_diff(&self.mirrors, &other.mirrors),
+            allowed_kinds: field_diff(&self.allowed_kinds, &other.allowed_kinds),
         }
     }
-}
-
-/// Group roles definition
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct GroupRoles {
-    /// List of role definitions
-    pub roles: Vec<Role>,
-}
 
-impl GroupRoles {
-    /// Create new empty roles
-    pub fn new() -> Self {
-        Self::default()
+    /// Check if the user-visible fields (name, about and picture) differ
+    pub fn visible_diff(&self, other: &GroupMetadata) -> bool {
+        self.name != other.name || self.about != other.about || self.picture != other.picture
     }
 
-    /// Add a role
-    pub fn add_role(mut self, role: Role) -> Self {
-        self.roles.push(role);
-        self
+    /// Compute a short fingerprint of the metadata (ETag-like)
+    ///
+    /// The fingerprint is the first 16 hex chars of the SHA256 of the sorted canonical tags,
+    /// so it doesn't depend on the order in which the tags were received.
+    pub fn fingerprint(&self) -> String {
+        let tags: Vec<Tag> = self.clone().into();
+        let mut tags: Vec<&[String]> = tags.iter().map(|t| t.as_slice()).collect();
+        tags.sort();
+
+        let hash: Sha256Hash = Sha256Hash::hash(json!(tags).to_string().as_bytes());
+        let mut fingerprint: String = hash.to_string();
+        fingerprint.truncate(16);
+        fingerprint
     }
 }
 
-impl From<GroupRoles> for Vec<Tag> {
-    fn from(roles: GroupRoles) -> Self {
-        roles
-            .roles
-            .into_iter()
-            .map(|role| {
-                if let Some(desc) = role.description {
-                    Tag::custom(TagKind::Custom("role".into()), [role.name, desc])
-                } else {
-                    Tag::custom(TagKind::Custom("role".into()), [role.name])
-                }
-            })
-            .collect()
+fn field_diff<T>(old: &T, new: &T) -> Option<(T, T)>
+where
+    T: Clone + PartialEq,
+{
+    if old == new {
+        None
+    } else {
+        Some((old.clone(), new.clone()))
     }
 }
 
-/// Group admin with assigned roles
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct GroupAdmin {
-    /// Public key of admin
-    pub public_key: PublicKey,
-    /// Assigned roles
-    pub roles: Vec<String>,
+/// Changes between two versions of [`GroupMetadata`]
+///
+/// Each changed field holds its `(old, new)` values. Unchanged fields are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupMetadataDiff {
+    /// Display name
+    pub name: Option<(Option<String>, Option<String>)>,
+    /// Description
+    pub about: Option<(Option<String>, Option<String>)>,
+    /// Group image URL
+    pub picture: Option<(Option<Url>, Option<Url>)>,
+    /// Privacy setting
+    pub privacy: Option<(Privacy, Privacy)>,
+    /// Access model
+    pub closed: Option<(AccessModel, AccessModel)>,
+    /// Language
+    pub language: Option<(Option<String>, Option<String>)>,
+    /// Rules URL
+    pub rules: Option<(Option<Url>, Option<Url>)>,
+    /// Whether the group is listed in public directories
+    pub discoverable: Option<(Option<bool>, Option<bool>)>,
+    /// Group owner
+    pub owner: Option<(Option<PublicKey>, Option<PublicKey>)>,
+    /// Topics
+    pub categories: Option<(Vec<String>, Vec<String>)>,
+    /// Mirror relays
+    pub mirrors: Option<(Vec<Url>, Vec<Url>)>,
+    /// Allowed event kinds
+    pub allowed_kinds: Option<(Vec<Kind>, Vec<Kind>)>,
 }
 
-impl GroupAdmin {
-    /// Create new admin with roles
-    pub fn new(public_key: PublicKey, roles: Vec<String>) -> Self {
-        Self { public_key, roles }
+impl GroupMetadataDiff {
+    /// Check if no field changed
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
     }
 }
 
-/// Group admins list
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct GroupAdmins {
-    /// List of admins
-    pub admins: Vec<GroupAdmin>,
+impl GroupMetadata {
+    /// Parse a group metadata event (kind 39000)
+    ///
+    /// In `lenient` mode the tag keys are lowercased before matching, for interoperability
+    /// with relays emitting e.g. `Privacy` or `Name` tags (see [`lowercase_tag_keys`]).
+    ///
+    /// Check [`GroupMetadata::from_tags`] for the parsing rules.
+    ///
+    /// Returns [`Error::MissingRequiredTag`] if the event is not a kind 39000 event.
+    pub fn parse(event: &Event, lenient: bool) -> Result<Self, Error> {
+        if event.kind != Kind::GroupMetadata {
+            return Err(Error::MissingRequiredTag(format!(
+                "expected a group metadata event (kind 39000), got kind {}",
+                event.kind
+            )));
+        }
+
+        if lenient {
+            Self::from_tags(lowercase_tag_keys(event.tags.iter()).iter())
+        } else {
+            Self::from_tags(event.tags.iter())
+        }
+    }
 }
 
-impl GroupAdmins {
-    /// Create new empty admins list
+impl TryFrom<&Event> for GroupMetadata {
+    type Error = Error;
+
+    /// Parse a group metadata event (kind 39000)
+    ///
+    /// Check [`GroupMetadata::from_tags`] for the parsing rules.
+    #[inline]
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        Self::parse(event, false)
+    }
+}
+
+/// Copy the tags with their keys lowercased
+///
+/// Tag keys are case-sensitive, but some relays emit e.g. `H` or `Privacy`: used by the
+/// lenient parsers.
+///
+/// Single-letter keys are kept as they are, since uppercase letters are distinct tags
+/// (e.g. `E`, `K` and `P` in NIP-22), except for `H`.
+pub fn lowercase_tag_keys<'a, I>(tags: I) -> Vec<Tag>
+where
+    I: IntoIterator<Item = &'a Tag>,
+{
+    tags.into_iter()
+        .map(|tag| match tag.as_slice().split_first() {
+            Some((key, values)) if needs_lowercase(key) => {
+                let key: String = key.to_lowercase();
+                Tag::custom(TagKind::from(key.as_str()), values.iter().cloned())
+            }
+            _ => tag.clone(),
+        })
+        .collect()
+}
+
+fn needs_lowercase(key: &str) -> bool {
+    if key.chars().count() > 1 {
+        key.chars().any(|c| c.is_uppercase())
+    } else {
+        key == "H"
+    }
+}
+
+/// Group metadata builder
+///
+/// # Example
+/// ```rust
+/// use nostr::nips::nip29::{GroupMetadataBuilder, Privacy};
+///
+/// let metadata = GroupMetadataBuilder::new()
+///     .name("Rust devs")
+///     .about("Rust developers")
+///     .picture("https://example.com/rust.png")
+///     .unwrap()
+///     .privacy(Privacy::Private)
+///     .build();
+/// assert_eq!(metadata.name.as_deref(), Some("Rust devs"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupMetadataBuilder {
+    metadata: GroupMetadata,
+}
+
+impl GroupMetadataBuilder {
+    /// New builder
+    #[inline]
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Add an admin
-    pub fn add_admin(mut self, admin: GroupAdmin) -> Self {
-        self.admins.push(admin);
+    /// Set display name
+    #[inline]
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.metadata.name = Some(name.into());
+        self
+    }
+
+    /// Set description
+    #[inline]
+    pub fn about<S>(mut self, about: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.metadata.about = Some(about.into());
+        self
+    }
+
+    /// Set picture URL
+    ///
+    /// Returns an error if the URL is invalid or if it isn't an `http`, `https`
+    /// or `data:image/...` URL. An empty value clears the picture.
+    pub fn picture(mut self, picture: &str) -> Result<Self, Error> {
+        self.metadata.picture = parse_picture(picture)?;
+        Ok(self)
+    }
+
+    /// Set privacy
+    #[inline]
+    pub fn privacy<P>(mut self, privacy: P) -> Self
+    where
+        P: Into<Privacy>,
+    {
+        self.metadata.privacy = privacy.into();
+        self
+    }
+
+    /// Set access model
+    #[inline]
+    pub fn closed<A>(mut self, closed: A) -> Self
+    where
+        A: Into<AccessModel>,
+    {
+        self.metadata.closed = closed.into();
+        self
+    }
+
+    /// Build [`GroupMetadata`]
+    #[inline]
+    pub fn build(self) -> GroupMetadata {
+        self.metadata
+    }
+}
+
+/// Parse a picture URL: an empty value is used to clear it
+fn parse_picture(value: &str) -> Result<Option<Url>, Error> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    let url = Url::parse(value)
+        .map_err(|e| Error::InvalidMetadata(format!("Invalid picture URL: {e}")))?;
+    validate_picture(&url)?;
+    Ok(Some(url))
+}
+
+/// Only allow `http`, `https` and `data:image/...` pictures, to avoid unsafe client rendering
+fn validate_picture(url: &Url) -> Result<(), Error> {
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        "data" if url.path().starts_with("image/") => Ok(()),
+        scheme => Err(Error::InvalidMetadata(format!(
+            "Unsupported picture URL scheme: {scheme}"
+        ))),
+    }
+}
+
+/// Check that a relay URL has a host and the `ws` or `wss` scheme
+fn validate_relay_url(url: &Url) -> Result<(), Error> {
+    let has_host: bool = url.host_str().is_some_and(|host| !host.is_empty());
+
+    if !has_host {
+        return Err(Error::InvalidRelayUrl(format!("missing host: {url}")));
+    }
+
+    match url.scheme() {
+        "ws" | "wss" => Ok(()),
+        scheme => Err(Error::InvalidRelayUrl(format!(
+            "expected ws or wss scheme, got: {scheme}"
+        ))),
+    }
+}
+
+/// Basic BCP-47 format check (e.g. `en`, `pt-BR`, `zh-Hant-TW`)
+fn validate_language(lang: &str) -> Result<(), Error> {
+    let mut subtags = lang.split('-');
+
+    let primary_is_valid: bool = subtags
+        .next()
+        .is_some_and(|s| (2..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic()));
+    let rest_is_valid: bool =
+        subtags.all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if !primary_is_valid || !rest_is_valid {
+        return Err(Error::InvalidMetadata(format!(
+            "Invalid language tag: '{lang}'"
+        )));
+    }
+
+    Ok(())
+}
+
+impl From<GroupMetadata> for Vec<Tag> {
+    fn from(metadata: GroupMetadata) -> Self {
+        let mut tags = Vec::new();
+
+        if let Some(name) = metadata.name {
+            tags.push(Tag::custom(TagKind::Name, [name]));
+        }
+
+        if let Some(about) = metadata.about {
+            tags.push(Tag::custom(TagKind::Description, [about]));
+        }
+
+        if let Some(picture) = metadata.picture {
+            tags.push(Tag::custom(TagKind::Image, [picture.to_string()]));
+        }
+
+        tags.push(Tag::custom(TagKind::Custom("privacy".into()), [metadata.privacy.as_str()]));
+        tags.push(Tag::custom(TagKind::Custom("closed".into()), [metadata.closed.as_str()]));
+
+        if let Some(language) = metadata.language {
+            tags.push(Tag::custom(TagKind::Custom("lang".into()), [language]));
+        }
+
+        if let Some(rules) = metadata.rules {
+            tags.push(Tag::custom(
+                TagKind::Custom("rules".into()),
+                [rules.to_string()],
+            ));
+        }
+
+        if let Some(discoverable) = metadata.discoverable {
+            tags.push(Tag::custom(
+                TagKind::Custom("discoverable".into()),
+                [discoverable.to_string()],
+            ));
+        }
+
+        for mirror in metadata.mirrors {
+            tags.push(Tag::custom(
+                TagKind::Custom("mirror".into()),
+                [mirror.to_string()],
+            ));
+        }
+
+        for category in metadata.categories {
+            tags.push(Tag::hashtag(category));
+        }
+
+        for kind in metadata.allowed_kinds {
+            tags.push(Tag::from_standardized_without_cell(TagStandard::Kind {
+                kind,
+                uppercase: false,
+            }));
+        }
+
+        if let Some(owner) = metadata.owner {
+            tags.push(Tag::custom(
+                TagKind::p(),
+                [owner.to_hex(), String::new(), OWNER_MARKER.to_string()],
+            ));
+        }
+
+        tags
+    }
+}
+
+/// Role permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Permission {
+    /// Add users and assign roles (kind 9000)
+    AddUser,
+    /// Remove users (kind 9001)
+    RemoveUser,
+    /// Edit group metadata (kind 9002)
+    EditMetadata,
+    /// Delete events (kind 9005)
+    DeleteEvent,
+    /// Create invites (kind 9009)
+    CreateInvite,
+    /// Delete the group (kind 9008)
+    DeleteGroup,
+}
+
+impl Permission {
+    /// Convert to string representation
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::AddUser => "add-user",
+            Self::RemoveUser => "remove-user",
+            Self::EditMetadata => "edit-metadata",
+            Self::DeleteEvent => "delete-event",
+            Self::CreateInvite => "create-invite",
+            Self::DeleteGroup => "delete-group",
+        }
+    }
+
+    /// Get the permission required to publish a moderation event of the given kind
+    ///
+    /// Returns `None` for kinds that aren't covered by a permission (e.g. create-group, kind 9007).
+    pub fn required_for(kind: Kind) -> Option<Self> {
+        match kind {
+            Kind::GroupPutUser => Some(Self::AddUser),
+            Kind::GroupRemoveUser => Some(Self::RemoveUser),
+            Kind::GroupEditMetadata => Some(Self::EditMetadata),
+            Kind::GroupDeleteEvent => Some(Self::DeleteEvent),
+            Kind::GroupCreateInvite => Some(Self::CreateInvite),
+            Kind::GroupDelete => Some(Self::DeleteGroup),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add-user" => Ok(Self::AddUser),
+            "remove-user" => Ok(Self::RemoveUser),
+            "edit-metadata" => Ok(Self::EditMetadata),
+            "delete-event" => Ok(Self::DeleteEvent),
+            "create-invite" => Ok(Self::CreateInvite),
+            "delete-group" => Ok(Self::DeleteGroup),
+            _ => Err(Error::InvalidPermission(format!("Unknown permission: {s}"))),
+        }
+    }
+}
+
+/// Role definition
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Role {
+    /// Role name
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Permissions
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+}
+
+impl Role {
+    /// Create a new role
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            permissions: Vec::new(),
+        }
+    }
+
+    /// Create a new role with description
+    pub fn with_description<S, D>(name: S, description: D) -> Self
+    where
+        S: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            description: Some(description.into()),
+            permissions: Vec::new(),
+        }
+    }
+
+    /// Add a permission
+    pub fn add_permission(mut self, permission: Permission) -> Self {
+        self.permissions.push(permission);
+        self
+    }
+
+    /// Sort and de-duplicate the permissions
+    ///
+    /// Makes equality checks independent of the permissions order.
+    pub fn with_sorted_permissions(mut self) -> Self {
+        self.permissions.sort();
+        self.permissions.dedup();
+        self
+    }
+
+    /// Validate the role name
+    ///
+    /// The name must be non-empty, contain no control characters and be at most
+    /// [`MAX_ROLE_NAME_LEN`] characters long.
+    pub fn validate_name(&self) -> Result<(), Error> {
+        if self.name.is_empty() {
+            return Err(Error::InvalidRole("Role name cannot be empty".into()));
+        }
+
+        if self.name.chars().any(char::is_control) {
+            return Err(Error::InvalidRole(format!(
+                "Role name contains control characters: {:?}",
+                self.name
+            )));
+        }
+
+        if self.name.chars().count() > MAX_ROLE_NAME_LEN {
+            return Err(Error::InvalidRole(format!(
+                "Role name is longer than {MAX_ROLE_NAME_LEN} characters"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Group roles definition
+///
+/// Roles are ranked by their position: the first role has the highest rank.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GroupRoles {
+    /// List of role definitions
+    pub roles: Vec<Role>,
+}
+
+impl GroupRoles {
+    /// Create new empty roles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a roles event (kind 39003)
+    ///
+    /// Each `role` tag has the name as first value, an optional description as second value
+    /// and the permissions as the remaining values. Unknown permissions (e.g. added by newer
+    /// relays) are skipped.
+    /// The order of the roles is preserved. Returns an error if a role has an empty name.
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::GroupRoles {
+            return Err(Error::WrongKind(event.kind));
+        }
+
+        Self::from_tags(event.tags.iter())
+    }
+
+    /// Parse roles from `role` tags
+    pub(crate) fn from_tags<'a, I>(tags: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let mut roles: Vec<Role> = Vec::new();
+
+        for tag in tags.into_iter() {
+            if tag.kind() != TagKind::Custom("role".into()) {
+                continue;
+            }
+
+            let name: &str = match tag.content() {
+                Some(name) if !name.is_empty() => name,
+                _ => return Err(Error::InvalidRole("Role name cannot be empty".into())),
+            };
+            let description: Option<String> =
+                tag.as_slice().get(2).filter(|d| !d.is_empty()).cloned();
+            let permissions: Vec<Permission> = tag
+                .as_slice()
+                .iter()
+                .skip(3)
+                .filter_map(|p| Permission::from_str(p).ok())
+                .collect();
+
+            roles.push(Role {
+                name: name.to_string(),
+                description,
+                permissions,
+            });
+        }
+
+        Ok(Self { roles })
+    }
+
+    /// Validate the role names
+    ///
+    /// See [`Role::validate_name`].
+    pub fn validate(&self) -> Result<(), Error> {
+        self.roles.iter().try_for_each(Role::validate_name)
+    }
+
+    /// Add a role
+    ///
+    /// If a role with the same name (case-insensitive) is already defined, it's replaced
+    /// in place, so the rank is preserved and the last definition wins.
+    pub fn add_role(mut self, role: Role) -> Self {
+        match self
+            .roles
+            .iter_mut()
+            .find(|r| r.name.eq_ignore_ascii_case(&role.name))
+        {
+            Some(existing) => *existing = role,
+            None => self.roles.push(role),
+        }
         self
     }
-}
 
-impl From<GroupAdmins> for Vec<Tag> {
-    fn from(admins: GroupAdmins) -> Self {
-        let mut tags = Vec::new();
+    /// Get the rank of a role (`0` is the highest)
+    ///
+    /// Returns `None` if the role is not defined.
+    #[inline]
+    pub fn rank(&self, name: &str) -> Option<usize> {
+        self.roles.iter().position(|r| r.name == name)
+    }
+
+    /// Get role by name
+    pub fn get(&self, name: &str) -> Option<&Role> {
+        self.roles.iter().find(|r| r.name == name)
+    }
+
+    /// Map role names to their definition
+    pub fn by_name(&self) -> BTreeMap<String, &Role> {
+        self.roles
+            .iter()
+            .map(|role| (role.name.clone(), role))
+            .collect()
+    }
+
+    /// Get the union of the permissions of the given roles
+    ///
+    /// Undefined roles are ignored.
+    pub fn effective_permissions(&self, role_names: &[String]) -> BTreeSet<Permission> {
+        role_names
+            .iter()
+            .filter_map(|name| self.get(name))
+            .flat_map(|role| role.permissions.iter().copied())
+            .collect()
+    }
+
+    /// Compare with a newer roles definition
+    ///
+    /// Returns the role names that were `(added, removed, changed)`.
+    /// Permissions order is not taken into account.
+    pub fn diff(&self, other: &GroupRoles) -> (Vec<String>, Vec<String>, Vec<String>) {
+        let mut added: Vec<String> = Vec::new();
+        let mut changed: Vec<String> = Vec::new();
+
+        for role in other.roles.iter() {
+            match self.get(&role.name) {
+                Some(old) => {
+                    if old.clone().with_sorted_permissions()
+                        != role.clone().with_sorted_permissions()
+                    {
+                        changed.push(role.name.clone());
+                    }
+                }
+                None => added.push(role.name.clone()),
+            }
+        }
+
+        let removed: Vec<String> = self
+            .roles
+            .iter()
+            .filter(|r| other.get(&r.name).is_none())
+            .map(|r| r.name.clone())
+            .collect();
+
+        (added, removed, changed)
+    }
+
+    /// Check if a role outranks another one
+    ///
+    /// Returns `false` if any of the two roles is not defined.
+    pub fn can_manage(&self, manager: &str, target: &str) -> bool {
+        match (self.rank(manager), self.rank(target)) {
+            (Some(manager), Some(target)) => manager < target,
+            _ => false,
+        }
+    }
+}
+
+impl From<GroupRoles> for Vec<Tag> {
+    fn from(roles: GroupRoles) -> Self {
+        roles
+            .roles
+            .into_iter()
+            .map(|role| {
+                let mut values: Vec<String> = vec![role.name];
+
+                // Permissions follow the description, which is left empty if missing
+                if role.description.is_some() || !role.permissions.is_empty() {
+                    values.push(role.description.unwrap_or_default());
+                }
+
+                values.extend(role.permissions.iter().map(|p| p.to_string()));
+
+                Tag::custom(TagKind::Custom("role".into()), values)
+            })
+            .collect()
+    }
+}
+
+/// Group admin with assigned roles
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GroupAdmin {
+    /// Public key of admin
+    pub public_key: PublicKey,
+    /// Assigned roles
+    pub roles: Vec<String>,
+}
+
+impl GroupAdmin {
+    /// Create new admin with roles
+    pub fn new(public_key: PublicKey, roles: Vec<String>) -> Self {
+        Self { public_key, roles }
+    }
+}
+
+/// Group admins list
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GroupAdmins {
+    /// List of admins
+    pub admins: Vec<GroupAdmin>,
+}
+
+impl GroupAdmins {
+    /// Create new empty admins list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an admins event (kind 39001)
+    ///
+    /// Each `p` tag starts a new admin and the following `role` tags are assigned to it,
+    /// mirroring the layout of `From<GroupAdmins> for Vec<Tag>`.
+    /// `role` tags preceding the first `p` tag are ignored.
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::GroupAdmins {
+            return Err(Error::WrongKind(event.kind));
+        }
+
+        Ok(Self::from_tags(event.tags.iter()))
+    }
+
+    /// Parse admins from `p` tags, each followed by its `role` tags
+    pub(crate) fn from_tags<'a, I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let mut admins: Vec<GroupAdmin> = Vec::new();
+
+        for tag in tags.into_iter() {
+            match tag.as_standardized() {
+                Some(TagStandard::PublicKey { public_key, .. }) => {
+                    admins.push(GroupAdmin::new(*public_key, Vec::new()));
+                }
+                Some(TagStandard::Role { name, .. }) => {
+                    if let Some(admin) = admins.last_mut() {
+                        admin.roles.push(name.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self { admins }
+    }
+
+    /// Add an admin
+    ///
+    /// If the public key is already an admin, the roles are merged (union).
+    pub fn add_admin(mut self, admin: GroupAdmin) -> Self {
+        merge_admin(&mut self.admins, admin);
+        self
+    }
+
+    /// Merge admins listed more than once, unioning their roles
+    ///
+    /// Each admin keeps the position of its first occurrence. Useful to handle relays
+    /// publishing the same public key multiple times.
+    pub fn coalesce(&mut self) {
+        let mut admins: Vec<GroupAdmin> = Vec::with_capacity(self.admins.len());
+
+        for admin in self.admins.drain(..) {
+            merge_admin(&mut admins, admin);
+        }
+
+        self.admins = admins;
+    }
+
+    /// Validate that every admin role is defined (and therefore ranked) in `roles`
+    pub fn validate_ranks(&self, roles: &GroupRoles) -> Result<(), Error> {
+        for admin in self.admins.iter() {
+            for role in admin.roles.iter() {
+                if roles.rank(role).is_none() {
+                    return Err(Error::UndefinedRole(role.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get admin by public key
+    pub fn get(&self, public_key: &PublicKey) -> Option<&GroupAdmin> {
+        self.admins.iter().find(|a| &a.public_key == public_key)
+    }
+
+    /// Compare with a newer admins list
+    ///
+    /// Returns the public keys that were `(promoted, demoted, role_changed)`.
+    /// Role order is not taken into account.
+    pub fn diff(&self, other: &GroupAdmins) -> (Vec<PublicKey>, Vec<PublicKey>, Vec<PublicKey>) {
+        let mut promoted: Vec<PublicKey> = Vec::new();
+        let mut role_changed: Vec<PublicKey> = Vec::new();
+
+        for admin in other.admins.iter() {
+            match self.get(&admin.public_key) {
+                Some(old) => {
+                    let old_roles: BTreeSet<&String> = old.roles.iter().collect();
+                    let new_roles: BTreeSet<&String> = admin.roles.iter().collect();
+                    if old_roles != new_roles {
+                        role_changed.push(admin.public_key);
+                    }
+                }
+                None => promoted.push(admin.public_key),
+            }
+        }
+
+        let demoted: Vec<PublicKey> = self
+            .admins
+            .iter()
+            .filter(|a| other.get(&a.public_key).is_none())
+            .map(|a| a.public_key)
+            .collect();
+
+        (promoted, demoted, role_changed)
+    }
+}
+
+/// Add an admin to the list, merging its roles into the existing entry (if any)
+fn merge_admin(admins: &mut Vec<GroupAdmin>, admin: GroupAdmin) {
+    match admins.iter_mut().find(|a| a.public_key == admin.public_key) {
+        Some(existing) => {
+            for role in admin.roles.into_iter() {
+                if !existing.roles.contains(&role) {
+                    existing.roles.push(role);
+                }
+            }
+        }
+        None => admins.push(admin),
+    }
+}
+
+impl From<GroupAdmins> for Vec<Tag> {
+    fn from(admins: GroupAdmins) -> Self {
+        let mut tags = Vec::new();
+
+        for admin in admins.admins {
+            // Add p tag for public key
+            tags.push(Tag::public_key(admin.public_key));
+
+            // Add role tags
+            for role in admin.roles {
+                tags.push(Tag::custom(TagKind::Custom("role".into()), [role]));
+            }
+        }
+
+        tags
+    }
+}
+
+/// Effective permissions of the group admins
+///
+/// Built from [`GroupAdmins`] and [`GroupRoles`]: the permissions of each admin are the union
+/// of the permissions of its roles. Roles not defined in [`GroupRoles`] are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupPermissionSet {
+    permissions: BTreeMap<PublicKey, BTreeSet<Permission>>,
+}
+
+impl GroupPermissionSet {
+    /// Resolve the permissions of the admins
+    pub fn new(admins: &GroupAdmins, roles: &GroupRoles) -> Self {
+        let mut permissions: BTreeMap<PublicKey, BTreeSet<Permission>> = BTreeMap::new();
+
+        for admin in admins.admins.iter() {
+            permissions
+                .entry(admin.public_key)
+                .or_default()
+                .extend(roles.effective_permissions(&admin.roles));
+        }
+
+        Self { permissions }
+    }
+
+    /// Get the permissions of a public key
+    ///
+    /// Returns `None` if the public key is not an admin.
+    pub fn get(&self, public_key: &PublicKey) -> Option<&BTreeSet<Permission>> {
+        self.permissions.get(public_key)
+    }
+
+    /// Check if a public key has a permission
+    pub fn has_permission(&self, public_key: &PublicKey, permission: Permission) -> bool {
+        self.get(public_key)
+            .is_some_and(|permissions| permissions.contains(&permission))
+    }
+}
+
+/// Group members list
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GroupMembers {
+    /// List of member public keys
+    pub members: Vec<PublicKey>,
+}
+
+impl GroupMembers {
+    /// Create new empty members list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a members event (kind 39002)
+    ///
+    /// Duplicated public keys are skipped, preserving the order of the first occurrence.
+    /// Non-`p` tags are ignored.
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::GroupMembers {
+            return Err(Error::WrongKind(event.kind));
+        }
+
+        Ok(Self::from_tags(event.tags.iter()))
+    }
+
+    /// Parse members from `p` tags, skipping duplicates
+    pub(crate) fn from_tags<'a, I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let mut members: Vec<PublicKey> = Vec::new();
+
+        for tag in tags.into_iter() {
+            if let Some(TagStandard::PublicKey { public_key, .. }) = tag.as_standardized() {
+                if !members.contains(public_key) {
+                    members.push(*public_key);
+                }
+            }
+        }
+
+        Self { members }
+    }
+
+    /// Add a member
+    pub fn add_member(mut self, public_key: PublicKey) -> Self {
+        self.members.push(public_key);
+        self
+    }
+
+    /// Check if public key is a member
+    #[inline]
+    pub fn contains(&self, public_key: &PublicKey) -> bool {
+        self.members.contains(public_key)
+    }
+
+    /// Serialize to a newline-delimited list of hex public keys
+    pub fn to_hex_list(&self) -> String {
+        self.members
+            .iter()
+            .map(|pk| pk.to_hex())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Parse a newline-delimited list of hex public keys
+    ///
+    /// Blank lines are skipped.
+    pub fn from_hex_list(list: &str) -> Result<Self, Error> {
+        let mut members: Vec<PublicKey> = Vec::new();
+
+        for (index, line) in list.lines().enumerate() {
+            let line: &str = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let public_key: PublicKey = PublicKey::from_hex(line)
+                .map_err(|e| Error::InvalidPublicKey(format!("line {}: {e}", index + 1)))?;
+            members.push(public_key);
+        }
+
+        Ok(Self { members })
+    }
+}
+
+impl From<GroupMembers> for Vec<Tag> {
+    fn from(members: GroupMembers) -> Self {
+        members
+            .members
+            .into_iter()
+            .map(Tag::public_key)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_id_valid() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url.clone(), "rust-devs".to_string()).unwrap();
+        assert_eq!(group_id.id, "rust-devs");
+        assert_eq!(group_id.relay_url, url);
+        assert!(!group_id.is_top_level());
+    }
+
+    #[test]
+    fn test_group_id_top_level() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url, "_".to_string()).unwrap();
+        assert!(group_id.is_top_level());
+    }
+
+    #[test]
+    fn test_group_id_top_level_constructor() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::top_level(url.clone()).unwrap();
+        assert!(group_id.is_top_level());
+        assert_eq!(group_id.id, "_");
+        assert_eq!(group_id, GroupId::new(url, "_".to_string()).unwrap());
+
+        let url = Url::parse("https://relay.example.com").unwrap();
+        assert!(matches!(
+            GroupId::top_level(url),
+            Err(Error::InvalidRelayUrl(..))
+        ));
+    }
+
+    #[test]
+    fn test_group_id_invalid() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+
+        // Invalid characters
+        assert!(GroupId::new(url.clone(), "Rust Devs".to_string()).is_err());
+        assert!(GroupId::new(url.clone(), "rust@devs".to_string()).is_err());
+        assert!(GroupId::new(url.clone(), "".to_string()).is_err());
+
+        // Valid
+        assert!(GroupId::new(url.clone(), "rust-devs-123_test".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_group_id_from_str() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        assert_eq!(group_id.id, "rust-devs");
+        // URL normalization adds trailing slash
+        assert_eq!(group_id.relay_url.as_str(), "wss://relay.example.com/");
+
+        // Invalid format
+        assert!(GroupId::from_str("no-delimiter").is_err());
+        assert!(GroupId::from_str("too'many'parts").is_err());
+    }
+
+    #[test]
+    fn test_group_id_to_string() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url, "rust-devs".to_string()).unwrap();
+        assert_eq!(group_id.to_string(), "wss://relay.example.com'rust-devs");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_group_id_from_event() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url.clone(), "rust-devs".to_string()).unwrap();
+
+        let event = EventBuilder::group_message(group_id.clone(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupId::from_event(&event, url.clone()).unwrap(), group_id);
+
+        // Bare id
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tag(Tag::group_id("rust-devs"))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupId::from_event(&event, url.clone()).unwrap(), group_id);
+
+        // Invalid id
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tag(Tag::group_id("Rust Devs"))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(matches!(
+            GroupId::from_event(&event, url.clone()),
+            Err(Error::InvalidGroupId(..))
+        ));
+
+        // Missing `h` tag
+        let event = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            GroupId::from_event(&event, url),
+            Err(Error::MissingRequiredTag("h".into()))
+        );
+    }
+
+    #[test]
+    fn test_group_id_relay_ws_url() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        assert_eq!(group_id.relay_ws_url().as_str(), "wss://relay.example.com/");
+        assert!(group_id.validate_relay_url().is_ok());
+
+        // Host-less URL
+        let group_id = GroupId {
+            relay_url: Url::parse("file:///var/relay").unwrap(),
+            id: "rust-devs".to_string(),
+        };
+        assert!(matches!(
+            group_id.validate_relay_url(),
+            Err(Error::InvalidRelayUrl(..))
+        ));
+
+        // Wrong scheme
+        let group_id = GroupId {
+            relay_url: Url::parse("https://relay.example.com").unwrap(),
+            id: "rust-devs".to_string(),
+        };
+        assert!(matches!(
+            group_id.validate_relay_url(),
+            Err(Error::InvalidRelayUrl(..))
+        ));
+    }
+
+    #[test]
+    fn test_group_id_relay_url_scheme() {
+        let url = Url::parse("ws://localhost:7777").unwrap();
+        assert!(GroupId::new(url, "rust-devs".to_string()).is_ok());
+
+        for url in ["https://relay.example.com", "file:///var/relay"] {
+            let url = Url::parse(url).unwrap();
+            assert!(matches!(
+                GroupId::new(url, "rust-devs".to_string()),
+                Err(Error::InvalidRelayUrl(..))
+            ));
+        }
+
+        assert!(matches!(
+            GroupId::from_str("https://relay.example.com'rust-devs"),
+            Err(Error::InvalidRelayUrl(..))
+        ));
+    }
+
+    #[test]
+    fn test_group_id_case_insensitive_host() {
+        let lower = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        let mixed = GroupId::from_str("wss://Relay.Example.COM'rust-devs").unwrap();
+        assert_eq!(lower, mixed);
+        assert_eq!(lower.cmp(&mixed), core::cmp::Ordering::Equal);
+        assert_eq!(mixed.to_string(), "wss://relay.example.com'rust-devs");
+
+        // Path case is preserved
+        let a = GroupId::from_str("wss://relay.example.com/Groups'rust-devs").unwrap();
+        let b = GroupId::from_str("wss://RELAY.example.com/groups'rust-devs").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.relay_url.path(), "/Groups");
+    }
+
+    #[test]
+    fn test_group_id_bech32() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        let encoded: String = group_id.to_bech32().unwrap();
+        assert!(encoded.starts_with("ngroup1"));
+        assert_eq!(GroupId::from_bech32(&encoded).unwrap(), group_id);
+
+        // Top-level group
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let top_level = GroupId::top_level(url).unwrap();
+        let decoded = GroupId::from_bech32(&top_level.to_bech32().unwrap()).unwrap();
+        assert!(decoded.is_top_level());
+        assert_eq!(decoded, top_level);
+
+        // Wrong prefix
+        let npub = "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy";
+        assert!(matches!(GroupId::from_bech32(npub), Err(Error::Bech32(..))));
+    }
+
+    #[test]
+    fn test_group_id_serde() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+
+        let json: String = serde_json::to_string(&group_id).unwrap();
+        assert_eq!(json, r#""wss://relay.example.com'rust-devs""#);
+        assert_eq!(serde_json::from_str::<GroupId>(&json).unwrap(), group_id);
+
+        assert!(serde_json::from_str::<GroupId>(r#""no-delimiter""#).is_err());
+        assert!(serde_json::from_str::<GroupId>(r#""wss://relay.example.com'Invalid""#).is_err());
+    }
+
+    #[test]
+    fn test_group_id_filter_tag_value() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        assert_eq!(group_id.filter_tag_value(), group_id.id);
+        assert_ne!(group_id.filter_tag_value(), group_id.to_tag_value());
+    }
+
+    #[test]
+    fn test_privacy() {
+        assert_eq!(Privacy::Public.as_str(), "public");
+        assert_eq!(Privacy::Private.as_str(), "private");
+
+        assert_eq!(Privacy::from_str("public").unwrap(), Privacy::Public);
+        assert_eq!(Privacy::from_str("private").unwrap(), Privacy::Private);
+        assert_eq!(Privacy::from_str("PUBLIC").unwrap(), Privacy::Public);
+        assert!(Privacy::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_access_model() {
+        assert_eq!(AccessModel::Open.as_str(), "open");
+        assert_eq!(AccessModel::Closed.as_str(), "closed");
+
+        assert_eq!(AccessModel::from_str("open").unwrap(), AccessModel::Open);
+        assert_eq!(AccessModel::from_str("closed").unwrap(), AccessModel::Closed);
+        assert!(AccessModel::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_group_metadata_tags() {
+        let metadata = GroupMetadata {
+            name: Some("Rust Developers".into()),
+            about: Some("A group for Rust enthusiasts".into()),
+            picture: Some(Url::parse("https://example.com/image.png").unwrap()),
+            privacy: Privacy::Public,
+            closed: AccessModel::Closed,
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.into();
+        assert_eq!(tags.len(), 5);
+    }
+
+    #[test]
+    fn test_group_metadata_language() {
+        let metadata = GroupMetadata {
+            name: Some("Rust Developers".into()),
+            language: Some("en".into()),
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        assert!(tags
+            .iter()
+            .any(|t| t.as_slice() == ["lang".to_string(), "en".to_string()]));
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
+
+        // Empty language (cleared)
+        let tags = vec![Tag::custom(TagKind::Custom("lang".into()), [""])];
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap().language, None);
+    }
+
+    #[test]
+    fn test_group_metadata_rules() {
+        let metadata = GroupMetadata {
+            rules: Some(Url::parse("https://example.com/rules").unwrap()),
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
+
+        let tags = vec![Tag::custom(TagKind::Custom("rules".into()), ["not a url"])];
+        assert!(matches!(
+            GroupMetadata::from_tags(&tags),
+            Err(Error::InvalidMetadata(..))
+        ));
+    }
+
+    #[test]
+    fn test_group_metadata_discoverable() {
+        for discoverable in [Some(true), Some(false), None] {
+            let metadata = GroupMetadata {
+                discoverable,
+                ..Default::default()
+            };
+
+            let tags: Vec<Tag> = metadata.clone().into();
+            assert_eq!(
+                tags.iter()
+                    .any(|t| t.kind() == TagKind::Custom("discoverable".into())),
+                discoverable.is_some()
+            );
+            assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
+        }
+
+        let tags = vec![Tag::custom(TagKind::Custom("discoverable".into()), ["yes"])];
+        assert!(GroupMetadata::from_tags(&tags).is_err());
+    }
+
+    #[test]
+    fn test_group_metadata_picture() {
+        let tags = vec![Tag::custom(
+            TagKind::Image,
+            ["https://example.com/image.png"],
+        )];
+        let metadata = GroupMetadata::from_tags(&tags).unwrap();
+        assert_eq!(
+            metadata.picture,
+            Some(Url::parse("https://example.com/image.png").unwrap())
+        );
+
+        let tags = vec![Tag::custom(
+            TagKind::Image,
+            ["data:image/png;base64,iVBORw0KGgo="],
+        )];
+        assert!(GroupMetadata::from_tags(&tags).is_ok());
+
+        for picture in [
+            "javascript:alert(1)",
+            "file:///etc/passwd",
+            "data:text/html,hi",
+        ] {
+            let tags = vec![Tag::custom(TagKind::Image, [picture])];
+            assert!(matches!(
+                GroupMetadata::from_tags(&tags),
+                Err(Error::InvalidMetadata(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_group_metadata_mirror() {
+        let tags = vec![Tag::custom(
+            TagKind::Custom("mirror".into()),
+            ["wss://mirror.example.com"],
+        )];
+        let metadata = GroupMetadata::from_tags(&tags).unwrap();
+        assert_eq!(
+            metadata.mirrors,
+            vec![Url::parse("wss://mirror.example.com").unwrap()]
+        );
+
+        for mirror in ["https://mirror.example.com", "mailto:admin@example.com"] {
+            let tags = vec![Tag::custom(TagKind::Custom("mirror".into()), [mirror])];
+            assert!(matches!(
+                GroupMetadata::from_tags(&tags),
+                Err(Error::InvalidRelayUrl(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_group_metadata_name_title_fallback() {
+        let tags = vec![Tag::custom(TagKind::Name, ["Rust"])];
+        let metadata = GroupMetadata::from_tags(&tags).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Rust"));
+
+        let tags = vec![Tag::custom(TagKind::Title, ["Rust"])];
+        let metadata = GroupMetadata::from_tags(&tags).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Rust"));
+
+        // `name` takes precedence over `title`
+        let tags = vec![
+            Tag::custom(TagKind::Title, ["Title"]),
+            Tag::custom(TagKind::Name, ["Name"]),
+        ];
+        let metadata = GroupMetadata::from_tags(&tags).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Name"));
+
+        // Canonical output uses `name`
+        let tags: Vec<Tag> = metadata.into();
+        assert_eq!(tags[0].kind(), TagKind::Name);
+    }
+
+    #[test]
+    fn test_group_metadata_visible_diff() {
+        let metadata = GroupMetadata {
+            name: Some("Rust".into()),
+            ..Default::default()
+        };
+
+        let other = GroupMetadata {
+            privacy: Privacy::Private,
+            ..metadata.clone()
+        };
+        assert!(!metadata.visible_diff(&other));
+
+        let other = GroupMetadata {
+            name: Some("Rust Developers".into()),
+            ..metadata.clone()
+        };
+        assert!(metadata.visible_diff(&other));
+    }
+
+    #[test]
+    fn test_role() {
+        let role = Role::new("admin");
+        assert_eq!(role.name, "admin");
+        assert_eq!(role.description, None);
+
+        let role = Role::with_description("moderator", "Can moderate messages");
+        assert_eq!(role.name, "moderator");
+        assert_eq!(role.description, Some("Can moderate messages".into()));
+    }
+
+    #[test]
+    fn test_permission() {
+        for permission in [
+            Permission::AddUser,
+            Permission::RemoveUser,
+            Permission::EditMetadata,
+            Permission::DeleteEvent,
+            Permission::CreateInvite,
+            Permission::DeleteGroup,
+        ] {
+            assert_eq!(
+                Permission::from_str(permission.as_str()).unwrap(),
+                permission
+            );
+        }
+
+        assert!(Permission::from_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_role_sorted_permissions() {
+        let a = Role::new("moderator")
+            .add_permission(Permission::DeleteEvent)
+            .add_permission(Permission::RemoveUser);
+        let b = Role::new("moderator")
+            .add_permission(Permission::RemoveUser)
+            .add_permission(Permission::DeleteEvent)
+            .add_permission(Permission::RemoveUser);
+
+        assert_ne!(a, b);
+        assert_eq!(a.with_sorted_permissions(), b.with_sorted_permissions());
+    }
+
+    #[test]
+    fn test_group_roles_by_name() {
+        let admin = Role::new("admin").add_permission(Permission::DeleteGroup);
+        let moderator = Role::with_description("moderator", "Can moderate");
+        let roles = GroupRoles::new()
+            .add_role(admin.clone())
+            .add_role(moderator.clone());
+
+        let map = roles.by_name();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["admin"], &admin);
+        assert_eq!(map["moderator"], &moderator);
+        assert!(!map.contains_key("member"));
+    }
+
+    #[test]
+    fn test_group_roles_effective_permissions() {
+        let roles = GroupRoles::new()
+            .add_role(
+                Role::new("moderator")
+                    .add_permission(Permission::DeleteEvent)
+                    .add_permission(Permission::RemoveUser),
+            )
+            .add_role(Role::new("inviter").add_permission(Permission::CreateInvite));
+
+        let permissions = roles.effective_permissions(&[
+            "moderator".to_string(),
+            "inviter".to_string(),
+            "undefined".to_string(),
+        ]);
+        assert_eq!(
+            permissions,
+            BTreeSet::from([
+                Permission::RemoveUser,
+                Permission::DeleteEvent,
+                Permission::CreateInvite
+            ])
+        );
+
+        assert!(roles.effective_permissions(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_group_roles_diff() {
+        let old = GroupRoles::new()
+            .add_role(
+                Role::new("moderator")
+                    .add_permission(Permission::DeleteEvent)
+                    .add_permission(Permission::RemoveUser),
+            )
+            .add_role(Role::new("janitor").add_permission(Permission::DeleteEvent))
+            .add_role(Role::new("member"));
+        let new = GroupRoles::new()
+            .add_role(
+                Role::new("moderator")
+                    .add_permission(Permission::RemoveUser)
+                    .add_permission(Permission::DeleteEvent),
+            )
+            .add_role(Role::new("janitor").add_permission(Permission::RemoveUser))
+            .add_role(Role::new("guest"));
+
+        let (added, removed, changed) = old.diff(&new);
+        assert_eq!(added, vec![String::from("guest")]);
+        assert_eq!(removed, vec![String::from("member")]);
+        assert_eq!(changed, vec![String::from("janitor")]);
+    }
+
+    #[test]
+    fn test_group_roles_tags() {
+        let roles = GroupRoles::new()
+            .add_role(Role::new("admin"))
+            .add_role(Role::with_description("moderator", "Can moderate"));
+
+        let tags: Vec<Tag> = roles.into();
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn test_group_roles_rank() {
+        let roles = GroupRoles::new()
+            .add_role(Role::new("admin"))
+            .add_role(Role::new("moderator"));
+
+        assert_eq!(roles.rank("admin"), Some(0));
+        assert_eq!(roles.rank("moderator"), Some(1));
+        assert_eq!(roles.rank("member"), None);
+
+        assert!(roles.can_manage("admin", "moderator"));
+        assert!(!roles.can_manage("moderator", "admin"));
+        assert!(!roles.can_manage("admin", "member"));
+    }
+
+    #[test]
+    fn test_group_admins_validate_ranks() {
+        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
+        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
+
+        let roles = GroupRoles::new()
+            .add_role(Role::new("admin"))
+            .add_role(Role::new("moderator"));
+
+        let admins = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(pk1, vec!["admin".into()]))
+            .add_admin(GroupAdmin::new(pk2, vec!["moderator".into()]));
+        assert!(admins.validate_ranks(&roles).is_ok());
+
+        let admins = admins.add_admin(GroupAdmin::new(pk2, vec!["janitor".into()]));
+        assert_eq!(
+            admins.validate_ranks(&roles),
+            Err(Error::UndefinedRole("janitor".into()))
+        );
+    }
+
+    #[test]
+    fn test_group_admins_tags() {
+        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
+        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
+
+        let admins = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(pk1, vec!["admin".into()]))
+            .add_admin(GroupAdmin::new(pk2, vec!["moderator".into(), "member".into()]));
+
+        let tags: Vec<Tag> = admins.into();
+        // 2 p tags + 1 role for pk1 + 2 roles for pk2 = 5 tags
+        assert_eq!(tags.len(), 5);
+    }
+
+    #[test]
+    fn test_group_admins_diff() {
+        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
+        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
+        let pk3 = PublicKey::from_slice(&[0x03; 32]).unwrap();
+
+        let old = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(pk1, vec!["admin".into()]))
+            .add_admin(GroupAdmin::new(pk2, vec!["moderator".into()]));
+        let new = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(pk2, vec!["admin".into()]))
+            .add_admin(GroupAdmin::new(pk3, vec!["moderator".into()]));
+
+        let (promoted, demoted, role_changed) = old.diff(&new);
+        assert_eq!(promoted, vec![pk3]);
+        assert_eq!(demoted, vec![pk1]);
+        assert_eq!(role_changed, vec![pk2]);
+    }
+
+    #[test]
+    fn test_group_members_hex_list() {
+        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
+        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
 
-        for admin in admins.admins {
-            // Add p tag for public key
-            tags.push(Tag::public_key(admin.public_key));
+        let members = GroupMembers::new().add_member(pk1).add_member(pk2);
 
-            // Add role tags
-            for role in admin.roles {
-                tags.push(Tag::custom(TagKind::Custom("role".into()), [role]));
-            }
-        }
+        let list: String = members.to_hex_list();
+        assert_eq!(list, format!("{}\n{}", pk1.to_hex(), pk2.to_hex()));
+        assert_eq!(GroupMembers::from_hex_list(&list).unwrap(), members);
 
-        tags
+        let list = format!("{}\nnot-a-pubkey", pk1.to_hex());
+        assert!(matches!(
+            GroupMembers::from_hex_list(&list),
+            Err(Error::InvalidPublicKey(..))
+        ));
     }
-}
 
-/// Group members list
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct GroupMembers {
-    /// List of member public keys
-    pub members: Vec<PublicKey>,
-}
+    #[test]
+    fn test_group_members_tags() {
+        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
+        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
 
-impl GroupMembers {
-    /// Create new empty members list
-    pub fn new() -> Self {
-        Self::default()
+        let members = GroupMembers::new()
+            .add_member(pk1)
+            .add_member(pk2);
+
+        let tags: Vec<Tag> = members.into();
+        assert_eq!(tags.len(), 2);
     }
 
-    /// Add a member
-    pub fn add_member(mut self, public_key: PublicKey) -> Self {
-        self.members.push(public_key);
-        self
+    #[test]
+    fn test_metadata_fingerprint() {
+        let name = Tag::custom(TagKind::Name, ["Nostr"]);
+        let about = Tag::custom(TagKind::Description, ["About nostr"]);
+        let privacy = Tag::custom(TagKind::Custom("privacy".into()), ["private"]);
+
+        let metadata = GroupMetadata::from_tags([&name, &about, &privacy]).unwrap();
+        let reordered = GroupMetadata::from_tags([&privacy, &about, &name]).unwrap();
+
+        let fingerprint: String = metadata.fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(fingerprint, reordered.fingerprint());
+        assert_eq!(fingerprint, metadata.fingerprint());
+
+        let renamed = GroupMetadata {
+            name: Some("Bitcoin".to_string()),
+            ..metadata
+        };
+        assert_ne!(fingerprint, renamed.fingerprint());
     }
-}
 
-impl From<GroupMembers> for Vec<Tag> {
-    fn from(members: GroupMembers) -> Self {
-        members
-            .members
-            .into_iter()
-            .map(Tag::public_key)
-            .collect()
+    #[test]
+    fn test_parse_group_list() {
+        let lines = [
+            "wss://relay.example.com'rust-devs",
+            "  wss://groups.example.com'nostr  ",
+            "",
+            "missing-delimiter",
+            "wss://relay.example.com'Invalid",
+        ];
+
+        let (group_ids, errors) = parse_group_list(&lines);
+        assert_eq!(group_ids.len(), 2);
+        assert_eq!(group_ids[0].id, "rust-devs");
+        assert_eq!(group_ids[1].id, "nostr");
+        assert_eq!(
+            group_ids[1].relay_url.host_str(),
+            Some("groups.example.com")
+        );
+
+        let indexes: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indexes, vec![3, 4]);
+        assert!(matches!(errors[0].1, Error::InvalidGroupIdentifier(_)));
+        assert!(matches!(errors[1].1, Error::InvalidGroupId(_)));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_metadata_owner() {
+        let owner =
+            PublicKey::from_hex("aa4fc8665f5696e33db7e1a572e3b0f5b3d615837b0f362dcb1c8068b098c7b4")
+                .unwrap();
+        let admin =
+            PublicKey::from_hex("79dff8f82963424e0bb02708a22e44b4980893e3a4be0fa3cb60a43b946764e3")
+                .unwrap();
 
+        let metadata = GroupMetadata {
+            name: Some("Nostr".to_string()),
+            owner: Some(owner),
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        let owner_tag = tags.iter().find(|t| t.kind() == TagKind::p()).unwrap();
+        assert_eq!(owner_tag.as_slice(), ["p", &owner.to_hex(), "", "owner"]);
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
+
+        // Admin `p` tags aren't owners
+        let admin_tag = Tag::custom(
+            TagKind::p(),
+            [admin.to_hex(), String::new(), "admin".to_string()],
+        );
+        let parsed = GroupMetadata::from_tags([&admin_tag]).unwrap();
+        assert_eq!(parsed.owner, None);
+    }
+
+    #[cfg(feature = "std")]
     #[test]
-    fn test_group_id_valid() {
-        let url = Url::parse("wss://relay.example.com").unwrap();
-        let group_id = GroupId::new(url.clone(), "rust-devs".to_string()).unwrap();
-        assert_eq!(group_id.id, "rust-devs");
-        assert_eq!(group_id.relay_url, url);
-        assert!(!group_id.is_top_level());
+    fn test_metadata_try_from_event() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+
+        let metadata = GroupMetadata {
+            name: Some("Rust devs".to_string()),
+            about: Some("Rust developers".to_string()),
+            picture: Some(Url::parse("https://example.com/rust.png").unwrap()),
+            privacy: Privacy::Private,
+            closed: AccessModel::Closed,
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        let event = EventBuilder::new(Kind::GroupMetadata, "")
+            .tag(Tag::identifier(group_id.id.clone()))
+            .tags(tags)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupMetadata::try_from(&event).unwrap(), metadata);
+
+        // Spec tag names and default privacy and access model
+        let event = EventBuilder::new(Kind::GroupMetadata, "")
+            .tags([
+                Tag::identifier(group_id.id.clone()),
+                Tag::custom(TagKind::Custom("about".into()), ["Rust developers"]),
+                Tag::custom(
+                    TagKind::Custom("picture".into()),
+                    ["https://example.com/rust.png"],
+                ),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+        let parsed = GroupMetadata::try_from(&event).unwrap();
+        assert_eq!(parsed.about, metadata.about);
+        assert_eq!(parsed.picture, metadata.picture);
+        assert_eq!(parsed.privacy, Privacy::Public);
+        assert_eq!(parsed.closed, AccessModel::Open);
+
+        // Wrong kind
+        let event = EventBuilder::group_message(group_id, "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(matches!(
+            GroupMetadata::try_from(&event),
+            Err(Error::MissingRequiredTag(..))
+        ));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_group_id_top_level() {
-        let url = Url::parse("wss://relay.example.com").unwrap();
-        let group_id = GroupId::new(url, "_".to_string()).unwrap();
-        assert!(group_id.is_top_level());
+    fn test_members_from_event() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        let pk1 = Keys::generate().public_key();
+        let pk2 = Keys::generate().public_key();
+
+        let members = GroupMembers::new().add_member(pk1).add_member(pk2);
+        let event = EventBuilder::group_members(group_id.clone(), members.clone())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupMembers::from_event(&event).unwrap(), members);
+
+        // Duplicates
+        let event = EventBuilder::group_members(group_id.clone(), members.clone().add_member(pk1))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupMembers::from_event(&event).unwrap(), members);
+
+        // Wrong kind
+        let event = EventBuilder::group_admins(group_id, GroupAdmins::new())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            GroupMembers::from_event(&event),
+            Err(Error::WrongKind(Kind::GroupAdmins))
+        );
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_group_id_invalid() {
-        let url = Url::parse("wss://relay.example.com").unwrap();
+    fn test_admins_from_event() {
+        use crate::{EventBuilder, Keys};
 
-        // Invalid characters
-        assert!(GroupId::new(url.clone(), "Rust Devs".to_string()).is_err());
-        assert!(GroupId::new(url.clone(), "rust@devs".to_string()).is_err());
-        assert!(GroupId::new(url.clone(), "".to_string()).is_err());
+        let keys = Keys::generate();
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        let pk1 = Keys::generate().public_key();
+        let pk2 = Keys::generate().public_key();
+        let pk3 = Keys::generate().public_key();
 
-        // Valid
-        assert!(GroupId::new(url.clone(), "rust-devs-123_test".to_string()).is_ok());
+        let admins = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(
+                pk1,
+                vec!["admin".into(), "moderator".into()],
+            ))
+            .add_admin(GroupAdmin::new(pk2, Vec::new()))
+            .add_admin(GroupAdmin::new(pk3, vec!["moderator".into()]));
+        let event = EventBuilder::group_admins(group_id.clone(), admins.clone())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupAdmins::from_event(&event).unwrap(), admins);
+
+        // Leading role tags
+        let event = EventBuilder::new(Kind::GroupAdmins, "")
+            .tag(Tag::identifier(group_id.id.clone()))
+            .tag(Tag::role("orphan", None))
+            .tags(Vec::<Tag>::from(admins.clone()))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupAdmins::from_event(&event).unwrap(), admins);
+
+        // Wrong kind
+        let event = EventBuilder::group_members(group_id, GroupMembers::new())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            GroupAdmins::from_event(&event),
+            Err(Error::WrongKind(Kind::GroupMembers))
+        );
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_group_id_from_str() {
+    fn test_roles_from_event() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
         let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
-        assert_eq!(group_id.id, "rust-devs");
-        // URL normalization adds trailing slash
-        assert_eq!(group_id.relay_url.as_str(), "wss://relay.example.com/");
 
-        // Invalid format
-        assert!(GroupId::from_str("no-delimiter").is_err());
-        assert!(GroupId::from_str("too'many'parts").is_err());
+        let roles = GroupRoles::new()
+            .add_role(Role::with_description("admin", "Full access"))
+            .add_role(Role::new("moderator"))
+            .add_role(Role::with_description("helper", "Answers questions"));
+        let event = EventBuilder::group_roles(group_id.clone(), roles.clone())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(GroupRoles::from_event(&event).unwrap(), roles);
+
+        // Empty name
+        let event = EventBuilder::new(Kind::GroupRoles, "")
+            .tags([Tag::identifier(group_id.id), Tag::role("", None)])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(matches!(
+            GroupRoles::from_event(&event),
+            Err(Error::InvalidRole(..))
+        ));
     }
 
     #[test]
-    fn test_group_id_to_string() {
-        let url = Url::parse("wss://relay.example.com").unwrap();
-        let group_id = GroupId::new(url, "rust-devs".to_string()).unwrap();
-        assert_eq!(group_id.to_string(), "wss://relay.example.com'rust-devs");
+    fn test_metadata_categories() {
+        let metadata = GroupMetadata {
+            name: Some("Rust devs".to_string()),
+            categories: vec!["rust".to_string(), "programming".to_string()],
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        let hashtags: Vec<&str> = tags
+            .iter()
+            .filter(|t| t.kind() == TagKind::t())
+            .filter_map(|t| t.content())
+            .collect();
+        assert_eq!(hashtags, vec!["rust", "programming"]);
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
+
+        // No categories
+        let metadata = GroupMetadata {
+            categories: Vec::new(),
+            ..metadata
+        };
+        let tags: Vec<Tag> = metadata.clone().into();
+        assert!(!tags.iter().any(|t| t.kind() == TagKind::t()));
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
     }
 
     #[test]
-    fn test_privacy() {
-        assert_eq!(Privacy::Public.as_str(), "public");
-        assert_eq!(Privacy::Private.as_str(), "private");
+    fn test_metadata_serde() {
+        let metadata = GroupMetadata {
+            name: Some("Rust devs".to_string()),
+            picture: Some(Url::parse("https://example.com/rust.png").unwrap()),
+            privacy: Privacy::Private,
+            closed: AccessModel::Closed,
+            ..Default::default()
+        };
 
-        assert_eq!(Privacy::from_str("public").unwrap(), Privacy::Public);
-        assert_eq!(Privacy::from_str("private").unwrap(), Privacy::Private);
-        assert_eq!(Privacy::from_str("PUBLIC").unwrap(), Privacy::Public);
-        assert!(Privacy::from_str("invalid").is_err());
+        let json: String = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"Rust devs","picture":"https://example.com/rust.png","privacy":"private","closed":"closed"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<GroupMetadata>(&json).unwrap(),
+            metadata
+        );
+
+        // Missing fields fall back to defaults
+        let metadata: GroupMetadata = serde_json::from_str(r#"{"name":"Rust devs"}"#).unwrap();
+        assert_eq!(metadata.privacy, Privacy::Public);
+        assert_eq!(metadata.closed, AccessModel::Open);
+
+        assert!(serde_json::from_str::<GroupMetadata>(r#"{"privacy":"secret"}"#).is_err());
     }
 
     #[test]
-    fn test_access_model() {
-        assert_eq!(AccessModel::Open.as_str(), "open");
-        assert_eq!(AccessModel::Closed.as_str(), "closed");
+    fn test_publish_targets() {
+        let group_id = GroupId::from_str("wss://relay.example.com'rust-devs").unwrap();
+        let mirror = Url::parse("wss://mirror.example.com").unwrap();
 
-        assert_eq!(AccessModel::from_str("open").unwrap(), AccessModel::Open);
-        assert_eq!(AccessModel::from_str("closed").unwrap(), AccessModel::Closed);
-        assert!(AccessModel::from_str("invalid").is_err());
+        assert_eq!(
+            publish_targets(&group_id, None),
+            vec![group_id.relay_url.clone()]
+        );
+        assert_eq!(
+            publish_targets(&group_id, Some(&GroupMetadata::default())),
+            vec![group_id.relay_url.clone()]
+        );
+
+        let metadata = GroupMetadata {
+            mirrors: vec![mirror.clone(), group_id.relay_url.clone()],
+            ..Default::default()
+        };
+        assert_eq!(
+            publish_targets(&group_id, Some(&metadata)),
+            vec![group_id.relay_url.clone(), mirror]
+        );
+
+        // Mirrors survive the tags round-trip
+        let tags: Vec<Tag> = metadata.clone().into();
+        assert_eq!(GroupMetadata::from_tags(&tags).unwrap(), metadata);
     }
 
     #[test]
-    fn test_group_metadata_tags() {
-        let metadata = GroupMetadata {
-            name: Some("Rust Developers".into()),
-            about: Some("A group for Rust enthusiasts".into()),
-            picture: Some(Url::parse("https://example.com/image.png").unwrap()),
-            privacy: Privacy::Public,
-            closed: AccessModel::Closed,
+    fn test_roles_permissions_tags() {
+        let roles = GroupRoles::new()
+            .add_role(
+                Role::with_description("admin", "Full access")
+                    .add_permission(Permission::AddUser)
+                    .add_permission(Permission::DeleteGroup),
+            )
+            .add_role(Role::new("moderator").add_permission(Permission::DeleteEvent))
+            .add_role(Role::new("member"));
+
+        let tags: Vec<Tag> = roles.clone().into();
+        assert_eq!(
+            tags[0].as_slice(),
+            ["role", "admin", "Full access", "add-user", "delete-group"]
+        );
+        assert_eq!(
+            tags[1].as_slice(),
+            ["role", "moderator", "", "delete-event"]
+        );
+        assert_eq!(tags[2].as_slice(), ["role", "member"]);
+        assert_eq!(GroupRoles::from_tags(&tags).unwrap(), roles);
+
+        // Unknown permissions are skipped
+        let tag = Tag::custom(
+            TagKind::Custom("role".into()),
+            ["admin", "", "launch-rockets", "delete-group"],
+        );
+        assert_eq!(
+            GroupRoles::from_tags([&tag]).unwrap(),
+            GroupRoles::new().add_role(Role::new("admin").add_permission(Permission::DeleteGroup))
+        );
+
+        // Roles serialized without permissions
+        let role: Role = serde_json::from_str(r#"{"name":"admin","description":null}"#).unwrap();
+        assert_eq!(role, Role::new("admin"));
+    }
+
+    #[test]
+    fn test_metadata_builder() {
+        let metadata = GroupMetadataBuilder::new()
+            .name("Rust devs")
+            .about("Rust developers")
+            .picture("https://example.com/rust.png")
+            .unwrap()
+            .privacy(Privacy::Private)
+            .closed(AccessModel::Closed)
+            .build();
+
+        assert_eq!(
+            metadata,
+            GroupMetadata {
+                name: Some("Rust devs".to_string()),
+                about: Some("Rust developers".to_string()),
+                picture: Some(Url::parse("https://example.com/rust.png").unwrap()),
+                privacy: Privacy::Private,
+                closed: AccessModel::Closed,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            GroupMetadataBuilder::new().build(),
+            GroupMetadata::default()
+        );
+        assert!(GroupMetadataBuilder::new().picture("not a url").is_err());
+        assert!(GroupMetadataBuilder::new()
+            .picture("javascript:alert(1)")
+            .is_err());
+    }
+
+    #[test]
+    fn test_admins_coalesce() {
+        let pk1 =
+            PublicKey::from_hex("aa4fc8665f5696e33db7e1a572e3b0f5b3d615837b0f362dcb1c8068b098c7b4")
+                .unwrap();
+        let pk2 =
+            PublicKey::from_hex("79dff8f82963424e0bb02708a22e44b4980893e3a4be0fa3cb60a43b946764e3")
+                .unwrap();
+
+        let admin: String = String::from("admin");
+        let moderator: String = String::from("moderator");
+
+        let mut admins = GroupAdmins {
+            admins: vec![
+                GroupAdmin::new(pk1, vec![admin.clone()]),
+                GroupAdmin::new(pk2, vec![moderator.clone()]),
+                GroupAdmin::new(pk1, vec![moderator.clone(), admin.clone()]),
+            ],
         };
+        admins.coalesce();
+
+        assert_eq!(
+            admins,
+            GroupAdmins::new()
+                .add_admin(GroupAdmin::new(pk1, vec![admin, moderator.clone()]))
+                .add_admin(GroupAdmin::new(pk2, vec![moderator]))
+        );
+    }
 
-        let tags: Vec<Tag> = metadata.into();
-        assert_eq!(tags.len(), 5);
+    #[test]
+    fn test_group_permission_set() {
+        let pk1 =
+            PublicKey::from_hex("aa4fc8665f5696e33db7e1a572e3b0f5b3d615837b0f362dcb1c8068b098c7b4")
+                .unwrap();
+        let pk2 =
+            PublicKey::from_hex("79dff8f82963424e0bb02708a22e44b4980893e3a4be0fa3cb60a43b946764e3")
+                .unwrap();
+
+        let roles = GroupRoles::new()
+            .add_role(Role::new("moderator").add_permission(Permission::DeleteEvent))
+            .add_role(Role::new("inviter").add_permission(Permission::CreateInvite));
+        let admins = GroupAdmins::new().add_admin(GroupAdmin::new(
+            pk1,
+            vec![
+                String::from("moderator"),
+                String::from("inviter"),
+                String::from("undefined"),
+            ],
+        ));
+
+        let set = GroupPermissionSet::new(&admins, &roles);
+        assert!(set.has_permission(&pk1, Permission::DeleteEvent));
+        assert!(set.has_permission(&pk1, Permission::CreateInvite));
+        assert!(!set.has_permission(&pk1, Permission::DeleteGroup));
+        assert!(!set.has_permission(&pk2, Permission::DeleteEvent));
+        assert_eq!(set.get(&pk1).map(|p| p.len()), Some(2));
+        assert_eq!(set.get(&pk2), None);
     }
 
     #[test]
-    fn test_role() {
-        let role = Role::new("admin");
-        assert_eq!(role.name, "admin");
-        assert_eq!(role.description, None);
+    fn test_role_validate_name() {
+        assert!(Role::new("moderator").validate_name().is_ok());
+        assert!(Role::new("Chief Moderator ✨").validate_name().is_ok());
 
-        let role = Role::with_description("moderator", "Can moderate messages");
-        assert_eq!(role.name, "moderator");
-        assert_eq!(role.description, Some("Can moderate messages".into()));
+        assert!(Role::new("").validate_name().is_err());
+        assert!(Role::new("mod\nerator").validate_name().is_err());
+        assert!(Role::new("mod\u{7}").validate_name().is_err());
+        assert!(Role::new("a".repeat(MAX_ROLE_NAME_LEN + 1))
+            .validate_name()
+            .is_err());
+
+        let roles = GroupRoles::new()
+            .add_role(Role::new("admin"))
+            .add_role(Role::new("moderator"));
+        assert!(roles.validate().is_ok());
+
+        let roles = roles.add_role(Role::new("\t"));
+        assert!(matches!(roles.validate(), Err(Error::InvalidRole(_))));
     }
 
     #[test]
-    fn test_group_roles_tags() {
+    fn test_pending_joins_filter() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url, "rust-devs".to_string()).unwrap();
+
+        let filter = group_id.pending_joins_filter();
+        assert_eq!(filter.kinds, Some(BTreeSet::from([Kind::GroupJoinRequest])));
+        assert_eq!(
+            filter
+                .generic_tags
+                .get(&SingleLetterTag::lowercase(Alphabet::H)),
+            Some(&BTreeSet::from([String::from("rust-devs")]))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pending_joins_filter_match_event() {
+        use crate::filter::MatchEventOptions;
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url.clone(), "rust-devs".to_string()).unwrap();
+        let other_group = GroupId::new(url, "other".to_string()).unwrap();
+        let filter = group_id.pending_joins_filter();
+
+        let join = EventBuilder::group_join_request(group_id.clone(), None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(filter.match_event(&join, MatchEventOptions::new()));
+
+        let other_join = EventBuilder::group_join_request(other_group, None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(!filter.match_event(&other_join, MatchEventOptions::new()));
+
+        let leave = EventBuilder::group_leave_request(group_id)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(!filter.match_event(&leave, MatchEventOptions::new()));
+    }
+
+    #[test]
+    fn test_metadata_allowed_kinds() {
+        let metadata = GroupMetadata {
+            name: Some(String::from("Pictures")),
+            allowed_kinds: vec![Kind::ChatMessage, Kind::from(20)],
+            ..Default::default()
+        };
+
+        let tags: Vec<Tag> = metadata.clone().into();
+        assert!(tags.iter().any(|t| t.as_slice() == ["k", "9"]));
+        assert!(tags.iter().any(|t| t.as_slice() == ["k", "20"]));
+
+        let parsed = GroupMetadata::from_tags(tags.iter()).unwrap();
+        assert_eq!(parsed, metadata);
+
+        assert!(parsed.allows_kind(Kind::ChatMessage));
+        assert!(parsed.allows_kind(Kind::from(20)));
+        assert!(!parsed.allows_kind(Kind::TextNote));
+
+        // No restriction
+        assert!(GroupMetadata::default().allows_kind(Kind::TextNote));
+
+        let tags = [Tag::parse(["k", "not-a-kind"]).unwrap()];
+        assert!(matches!(
+            GroupMetadata::from_tags(tags.iter()),
+            Err(Error::InvalidMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn test_roles_add_role_replaces() {
         let roles = GroupRoles::new()
             .add_role(Role::new("admin"))
-            .add_role(Role::with_description("moderator", "Can moderate"));
+            .add_role(Role::new("moderator"))
+            .add_role(Role::with_description("Admin", "Group administrator"));
+
+        assert_eq!(
+            roles.roles,
+            vec![
+                Role::with_description("Admin", "Group administrator"),
+                Role::new("moderator"),
+            ]
+        );
+        assert_eq!(roles.rank("Admin"), Some(0));
 
         let tags: Vec<Tag> = roles.into();
         assert_eq!(tags.len(), 2);
     }
 
     #[test]
-    fn test_group_admins_tags() {
-        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
-        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
+    fn test_admins_add_admin_merges_roles() {
+        let pk =
+            PublicKey::from_hex("aa4fc8665f5696e33db7e1a572e3b0f5b3d615837b0f362dcb1c8068b098c7b4")
+                .unwrap();
 
         let admins = GroupAdmins::new()
-            .add_admin(GroupAdmin::new(pk1, vec!["admin".into()]))
-            .add_admin(GroupAdmin::new(pk2, vec!["moderator".into(), "member".into()]));
+            .add_admin(GroupAdmin::new(pk, vec![String::from("moderator")]))
+            .add_admin(GroupAdmin::new(pk, vec![String::from("admin")]));
+
+        assert_eq!(
+            admins.admins,
+            vec![GroupAdmin::new(
+                pk,
+                vec![String::from("moderator"), String::from("admin")]
+            )]
+        );
 
         let tags: Vec<Tag> = admins.into();
-        // 2 p tags + 1 role for pk1 + 2 roles for pk2 = 5 tags
-        assert_eq!(tags.len(), 5);
+        assert_eq!(tags.iter().filter(|t| t.kind() == TagKind::p()).count(), 1);
     }
 
     #[test]
-    fn test_group_members_tags() {
-        let pk1 = PublicKey::from_slice(&[0x01; 32]).unwrap();
-        let pk2 = PublicKey::from_slice(&[0x02; 32]).unwrap();
+    fn test_group_id_filters() {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url, "rust-devs".to_string()).unwrap();
+        let bare = BTreeSet::from([String::from("rust-devs")]);
+
+        let filter = group_id.subscription_filter();
+        let kinds = filter.kinds.as_ref().unwrap();
+        assert_eq!(kinds.len(), 10);
+        assert!(kinds.contains(&Kind::ChatMessage));
+        assert!(kinds.contains(&Kind::GroupPutUser));
+        assert!(kinds.contains(&Kind::GroupLeaveRequest));
+        assert!(!kinds.contains(&Kind::GroupMetadata));
+        assert_eq!(
+            filter
+                .generic_tags
+                .get(&SingleLetterTag::lowercase(Alphabet::H)),
+            Some(&bare)
+        );
+
+        let filter = group_id.metadata_filter();
+        assert_eq!(
+            filter.kinds,
+            Some(BTreeSet::from([
+                Kind::GroupMetadata,
+                Kind::GroupAdmins,
+                Kind::GroupMembers,
+                Kind::GroupRoles,
+            ]))
+        );
+        assert_eq!(
+            filter
+                .generic_tags
+                .get(&SingleLetterTag::lowercase(Alphabet::D)),
+            Some(&bare)
+        );
+        assert!(!filter
+            .generic_tags
+            .contains_key(&SingleLetterTag::lowercase(Alphabet::H)));
+    }
 
-        let members = GroupMembers::new()
-            .add_member(pk1)
-            .add_member(pk2);
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_subscription_filter_match_event() {
+        use crate::filter::MatchEventOptions;
+        use crate::{EventBuilder, Keys};
 
-        let tags: Vec<Tag> = members.into();
-        assert_eq!(tags.len(), 2);
+        let keys = Keys::generate();
+        let user = Keys::generate().public_key();
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let group_id = GroupId::new(url.clone(), "rust-devs".to_string()).unwrap();
+        let other_group = GroupId::new(url, "other".to_string()).unwrap();
+        let filter = group_id.subscription_filter();
+
+        let message = EventBuilder::group_message(group_id.clone(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let events = [
+            message.clone(),
+            EventBuilder::group_put_user(group_id.clone(), user, Vec::new())
+                .sign_with_keys(&keys)
+                .unwrap(),
+            EventBuilder::group_delete_event(group_id.clone(), message.id)
+                .sign_with_keys(&keys)
+                .unwrap(),
+            EventBuilder::group_create_invite(group_id.clone())
+                .sign_with_keys(&keys)
+                .unwrap(),
+            EventBuilder::group_join_request(group_id.clone(), None::<String>)
+                .sign_with_keys(&keys)
+                .unwrap(),
+            EventBuilder::group_leave_request(group_id.clone())
+                .sign_with_keys(&keys)
+                .unwrap(),
+        ];
+        for event in events.iter() {
+            assert!(filter.match_event(event, MatchEventOptions::new()));
+        }
+
+        // Other group
+        let other = EventBuilder::group_message(other_group, "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(!filter.match_event(&other, MatchEventOptions::new()));
+
+        // Addressable state events
+        let metadata = EventBuilder::group_metadata(group_id, GroupMetadata::default())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(!filter.match_event(&metadata, MatchEventOptions::new()));
+    }
+
+    #[test]
+    fn test_metadata_diff_tags() {
+        let old = GroupMetadata {
+            name: Some(String::from("Rust")),
+            about: Some(String::from("Old description")),
+            categories: vec![String::from("rust")],
+            ..Default::default()
+        };
+
+        let mut new = old.clone();
+        new.about = Some(String::from("New description"));
+        assert_eq!(
+            old.diff_tags(&new),
+            vec![Tag::custom(TagKind::Description, ["New description"])]
+        );
+
+        new.privacy = Privacy::Private;
+        new.categories.push(String::from("dev"));
+        assert_eq!(
+            old.diff_tags(&new),
+            vec![
+                Tag::custom(TagKind::Description, ["New description"]),
+                Tag::custom(TagKind::Custom("privacy".into()), ["private"]),
+                Tag::hashtag("rust"),
+                Tag::hashtag("dev"),
+            ]
+        );
+
+        assert!(old.diff_tags(&old).is_empty());
+    }
+
+    #[test]
+    fn test_metadata_diff_tags_cleared() {
+        let old = GroupMetadata {
+            name: Some(String::from("Rust")),
+            about: Some(String::from("Rust developers")),
+            picture: Some(Url::parse("https://example.com/rust.png").unwrap()),
+            ..Default::default()
+        };
+
+        let new = GroupMetadata {
+            about: None,
+            picture: None,
+            ..old.clone()
+        };
+        assert_eq!(
+            old.diff_tags(&new),
+            vec![
+                Tag::custom(TagKind::Description, [""]),
+                Tag::custom(TagKind::Image, [""]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metadata_diff_tags_round_trip() {
+        // Apply the diff over the old tags, replacing the tags of the same kind
+        fn apply(old: &GroupMetadata, new: &GroupMetadata) -> GroupMetadata {
+            let diff: Vec<Tag> = old.diff_tags(new);
+            let old: Vec<Tag> = old.clone().into();
+            let tags: Vec<Tag> = old
+                .into_iter()
+                .filter(|t| !diff.iter().any(|d| d.kind() == t.kind()))
+                .chain(diff.iter().cloned())
+                .collect();
+            GroupMetadata::from_tags(&tags).unwrap()
+        }
+
+        let full = GroupMetadata {
+            name: Some(String::from("Rust")),
+            about: Some(String::from("Rust developers")),
+            picture: Some(Url::parse("https://example.com/rust.png").unwrap()),
+            privacy: Privacy::Private,
+            closed: AccessModel::Closed,
+            language: Some(String::from("en")),
+            rules: Some(Url::parse("https://example.com/rules").unwrap()),
+            discoverable: Some(true),
+            owner: Some(
+                PublicKey::from_hex(
+                    "aa4fc8665f5696e33db7e1a572e3b0f5b3d615837b0f362dcb1c8068b098c7b4",
+                )
+                .unwrap(),
+            ),
+            categories: vec![String::from("rust"), String::from("dev")],
+            mirrors: vec![Url::parse("wss://mirror.example.com").unwrap()],
+            allowed_kinds: vec![Kind::ChatMessage, Kind::TextNote],
+        };
+        let changed = GroupMetadata {
+            name: Some(String::from("Rustaceans")),
+            about: Some(String::from("Rust developers and friends")),
+            picture: Some(Url::parse("https://example.com/crab.png").unwrap()),
+            privacy: Privacy::Public,
+            closed: AccessModel::Open,
+            language: Some(String::from("pt-BR")),
+            rules: Some(Url::parse("https://example.com/rules-v2").unwrap()),
+            discoverable: Some(false),
+            owner: Some(
+                PublicKey::from_hex(
+                    "79dff8f82963424e0bb02708a22e44b4980893e3a4be0fa3cb60a43b946764e3",
+                )
+                .unwrap(),
+            ),
+            categories: vec![String::from("rust")],
+            mirrors: vec![Url::parse("wss://other.example.com").unwrap()],
+            allowed_kinds: vec![Kind::ChatMessage],
+        };
+        let empty = GroupMetadata::default();
+
+        // Changed, cleared and set fields
+        assert_eq!(apply(&full, &changed), changed);
+        assert_eq!(apply(&full, &empty), empty);
+        assert_eq!(apply(&empty, &full), full);
+
+        // Cleared privacy and access model are written as their defaults
+        let diff: Vec<Tag> = full.diff_tags(&empty);
+        assert!(diff.contains(&Tag::custom(TagKind::Custom("privacy".into()), ["public"])));
+        assert!(diff.contains(&Tag::custom(TagKind::Custom("closed".into()), ["open"])));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_metadata_parse_lenient() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let event = EventBuilder::new(Kind::GroupMetadata, "")
+            .tags([
+                Tag::identifier("rust-devs"),
+                Tag::parse(["Name", "Rust devs"]).unwrap(),
+                Tag::parse(["PRIVACY", "private"]).unwrap(),
+                Tag::parse(["Closed", "closed"]).unwrap(),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let strict = GroupMetadata::parse(&event, false).unwrap();
+        assert_eq!(strict.name, None);
+        assert_eq!(strict.privacy, Privacy::Public);
+
+        let lenient = GroupMetadata::parse(&event, true).unwrap();
+        assert_eq!(lenient.name.as_deref(), Some("Rust devs"));
+        assert_eq!(lenient.privacy, Privacy::Private);
+        assert_eq!(lenient.closed, AccessModel::Closed);
+    }
+
+    #[test]
+    fn test_lowercase_tag_keys() {
+        let tags = [
+            Tag::parse(["Privacy", "private"]).unwrap(),
+            Tag::parse(["H", "rust-devs"]).unwrap(),
+            Tag::parse(["E", "root"]).unwrap(),
+            Tag::parse(["K", "9"]).unwrap(),
+            Tag::parse(["name", "Rust devs"]).unwrap(),
+        ];
+
+        let keys: Vec<String> = lowercase_tag_keys(tags.iter())
+            .iter()
+            .map(|t| t.as_slice()[0].clone())
+            .collect();
+        assert_eq!(keys, ["privacy", "h", "E", "K", "name"]);
+    }
+
+    #[test]
+    fn test_metadata_diff() {
+        let old = GroupMetadata {
+            name: Some(String::from("Rust")),
+            about: Some(String::from("Rust developers")),
+            ..Default::default()
+        };
+
+        // Name only
+        let new = GroupMetadata {
+            name: Some(String::from("Rust devs")),
+            ..old.clone()
+        };
+        assert_eq!(
+            old.diff(&new),
+            GroupMetadataDiff {
+                name: Some((Some(String::from("Rust")), Some(String::from("Rust devs")))),
+                ..Default::default()
+            }
+        );
+
+        // Privacy and access model
+        let new = GroupMetadata {
+            privacy: Privacy::Private,
+            closed: AccessModel::Closed,
+            ..old.clone()
+        };
+        assert_eq!(
+            old.diff(&new),
+            GroupMetadataDiff {
+                privacy: Some((Privacy::Public, Privacy::Private)),
+                closed: Some((AccessModel::Open, AccessModel::Closed)),
+                ..Default::default()
+            }
+        );
+
+        assert!(old.diff(&old).is_empty());
+        assert!(!old.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn test_privacy_and_access_model_helpers() {
+        assert!(Privacy::Public.is_public());
+        assert!(!Privacy::Private.is_public());
+
+        assert!(AccessModel::Closed.requires_approval());
+        assert!(!AccessModel::Open.requires_approval());
+    }
+
+    #[test]
+    fn test_group_id_validate_id() {
+        assert!(GroupId::validate_id("rust-devs_2").is_ok());
+        assert!(GroupId::validate_id(TOP_LEVEL_GROUP_ID).is_ok());
+
+        assert!(matches!(
+            GroupId::validate_id(""),
+            Err(Error::InvalidGroupId(_))
+        ));
+        assert!(GroupId::validate_id("Rust").is_err());
+        assert!(GroupId::validate_id("rust devs").is_err());
+        assert!(GroupId::validate_id("rust'devs").is_err());
     }
 }
@@ -0,0 +1,985 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2025 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP-29: Validation
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{Event, EventId, Kind, PublicKey, TagKind, TagStandard};
+
+use super::constants::MIN_PREVIOUS_REF_LEN;
+use super::state::put_user_assignments;
+use super::{
+    Error, GroupAdmin, GroupAdmins, GroupId, GroupMembers, GroupMetadata, GroupRoles, Permission,
+};
+
+/// Authorization check result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AuthCheck {
+    /// The author had authority
+    Authorized,
+    /// The author is not an admin
+    NotAdmin,
+    /// The author is an admin, but none of its roles are defined
+    UndefinedRole,
+    /// The author is an admin, but its roles don't grant the required permission
+    MissingPermission(Permission),
+}
+
+impl AuthCheck {
+    /// Check if authorized
+    #[inline]
+    pub fn is_authorized(&self) -> bool {
+        matches!(self, Self::Authorized)
+    }
+}
+
+fn check_authority(
+    admins: &GroupAdmins,
+    roles: &GroupRoles,
+    author: &PublicKey,
+    kind: Kind,
+) -> AuthCheck {
+    let Some(admin) = admins.get(author) else {
+        return AuthCheck::NotAdmin;
+    };
+
+    if !admin.roles.iter().any(|r| roles.rank(r).is_some()) {
+        return AuthCheck::UndefinedRole;
+    }
+
+    let Some(permission) = Permission::required_for(kind) else {
+        return AuthCheck::Authorized;
+    };
+
+    if roles
+        .effective_permissions(&admin.roles)
+        .contains(&permission)
+    {
+        AuthCheck::Authorized
+    } else {
+        AuthCheck::MissingPermission(permission)
+    }
+}
+
+/// Validate a chain of moderation events
+///
+/// Events are processed in the given order, starting from the provided `admins`.
+/// The author must hold a role granting the permission required by the event kind
+/// (see [`Permission::required_for`]). Authorized put-user (kind 9000) and remove-user (kind 9001) events update the admins list,
+/// so each event is checked against the authority its author had at that point.
+///
+/// Non-moderation events are skipped.
+pub fn validate_chain(
+    events: &[Event],
+    admins: &GroupAdmins,
+    roles: &GroupRoles,
+) -> Vec<(EventId, AuthCheck)> {
+    let mut admins: GroupAdmins = admins.clone();
+    let mut checks: Vec<(EventId, AuthCheck)> = Vec::new();
+
+    for event in events.iter().filter(|e| e.kind.is_group_moderation()) {
+        let check: AuthCheck = check_authority(&admins, roles, &event.pubkey, event.kind);
+        checks.push((event.id, check));
+
+        if !check.is_authorized() {
+            continue;
+        }
+
+        match event.kind {
+            Kind::GroupPutUser => {
+                for (public_key, user_roles) in put_user_assignments(event) {
+                    admins.admins.retain(|a| a.public_key != public_key);
+
+                    if !user_roles.is_empty() {
+                        admins.admins.push(GroupAdmin::new(public_key, user_roles));
+                    }
+                }
+            }
+            Kind::GroupRemoveUser => {
+                for public_key in event.tags.public_keys() {
+                    admins.admins.retain(|a| &a.public_key != public_key);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    checks
+}
+
+/// Extract the `d` tag of an addressable group event (kinds 39000-39003)
+///
+/// Returns an error if the event isn't an addressable group event
+/// or if the `d` tag is missing or empty.
+pub fn addressable_identifier(event: &Event) -> Result<String, Error> {
+    if !event.kind.is_group_metadata() {
+        return Err(Error::WrongKind(event.kind));
+    }
+
+    match event.tags.identifier() {
+        Some("") => Err(Error::InvalidGroupId("Group ID cannot be empty".into())),
+        Some(id) => Ok(id.to_string()),
+        None => Err(Error::MissingRequiredTag("d".into())),
+    }
+}
+
+/// Validate that the addressable events of a group are consistent
+///
+/// Each event must have the expected kind (39000, 39001, 39002 and 39003 respectively)
+/// and all of them must share the same `d` tag.
+pub fn validate_state_set(
+    metadata: &Event,
+    admins: &Event,
+    members: &Event,
+    roles: &Event,
+) -> Result<(), Error> {
+    let set = [
+        (metadata, Kind::GroupMetadata),
+        (admins, Kind::GroupAdmins),
+        (members, Kind::GroupMembers),
+        (roles, Kind::GroupRoles),
+    ];
+
+    for (event, kind) in set.iter() {
+        if &event.kind != kind {
+            return Err(Error::WrongKind(event.kind));
+        }
+    }
+
+    let expected: String = addressable_identifier(metadata)?;
+
+    for (event, ..) in set.iter().skip(1) {
+        let found: String = addressable_identifier(event)?;
+
+        if found != expected {
+            return Err(Error::GroupMismatch { expected, found });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if two addressable group events conflict
+///
+/// Events of the same kind and with the same `d` tag occupy the same addressable slot:
+/// if they come from the same author, the newest simply replaces the other one.
+/// If they come from different authors, they conflict.
+pub fn events_conflict(a: &Event, b: &Event) -> bool {
+    a.kind == b.kind
+        && a.kind.is_group_metadata()
+        && a.pubkey != b.pubkey
+        && a.tags.identifier().is_some()
+        && a.tags.identifier() == b.tags.identifier()
+}
+
+/// Check if two group IDs collide on the same relay
+///
+/// Group IDs can only contain lowercase characters (see [`GroupId::new`]),
+/// so an exact comparison is equivalent to a case-insensitive one.
+pub fn ids_collide(a: &GroupId, b: &GroupId) -> bool {
+    a.relay_url == b.relay_url && a.id == b.id
+}
+
+fn group_tag(event: &Event) -> Option<&str> {
+    event.tags.find(TagKind::h()).and_then(|t| t.content())
+}
+
+/// Validate a create-group event (kind 9007)
+///
+/// The event must have an `h` tag and parseable metadata tags.
+/// Returns the parsed metadata.
+pub fn validate_create_event(event: &Event) -> Result<GroupMetadata, Error> {
+    if event.kind != Kind::GroupCreate {
+        return Err(Error::WrongKind(event.kind));
+    }
+
+    if group_tag(event).is_none() {
+        return Err(Error::MissingRequiredTag("h".into()));
+    }
+
+    GroupMetadata::from_tags(event.tags.iter())
+}
+
+/// Validate that a delete-event (kind 9005) targets an event of the same group
+///
+/// Both events must have the same `h` tag.
+pub fn validate_delete_target(delete_event: &Event, target_event: &Event) -> Result<(), Error> {
+    if delete_event.kind != Kind::GroupDeleteEvent {
+        return Err(Error::WrongKind(delete_event.kind));
+    }
+
+    let expected: &str =
+        group_tag(delete_event).ok_or_else(|| Error::MissingRequiredTag("h".into()))?;
+    let found: &str =
+        group_tag(target_event).ok_or_else(|| Error::MissingRequiredTag("h".into()))?;
+
+    if expected != found {
+        return Err(Error::GroupMismatch {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that the timeline references of a message only point to events of the same group
+///
+/// Each `previous` reference is resolved against the `known` events: references that can't be
+/// resolved are skipped. So are references shorter than [`MIN_PREVIOUS_REF_LEN`] hex characters
+/// and ambiguous ones (matching more than one known event).
+pub fn validate_previous_refs(message: &Event, known: &[Event]) -> Result<(), Error> {
+    let group: Option<&str> = group_tag(message);
+
+    for tag in message.tags.iter() {
+        if let Some(TagStandard::Previous(refs)) = tag.as_standardized() {
+            for reference in refs.iter() {
+                if let Some(target) = resolve_previous_ref(reference, known) {
+                    if group_tag(target) != group {
+                        return Err(Error::CrossGroupReference(reference.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the only known event matching a timeline reference
+fn resolve_previous_ref<'a>(reference: &str, known: &'a [Event]) -> Option<&'a Event> {
+    let is_valid: bool =
+        reference.len() >= MIN_PREVIOUS_REF_LEN && reference.chars().all(|c| c.is_ascii_hexdigit());
+
+    if !is_valid {
+        return None;
+    }
+
+    let mut candidates = known
+        .iter()
+        .filter(|e| e.id.to_hex().starts_with(reference));
+    let target: &Event = candidates.next()?;
+
+    if candidates.any(|e| e.id != target.id) {
+        return None;
+    }
+
+    Some(target)
+}
+
+fn invite_code(event: &Event) -> Option<&str> {
+    event
+        .tags
+        .find(TagKind::Custom("code".into()))
+        .and_then(|t| t.content())
+}
+
+/// Validate an invite code
+///
+/// The code must be non-empty and contain only URL-safe characters (`A-Z`, `a-z`, `0-9`,
+/// `-`, `_`, `.` and `~`), so that it can be shared in links.
+pub fn validate_invite_code(code: &str) -> Result<(), Error> {
+    let url_safe = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~');
+
+    if code.is_empty() || !code.chars().all(url_safe) {
+        return Err(Error::InvalidInviteCode(code.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate that a message doesn't have more than `max` timeline references (`previous` tag)
+pub fn validate_previous_count(event: &Event, max: usize) -> Result<(), Error> {
+    let count: usize = event
+        .tags
+        .iter()
+        .filter_map(|tag| match tag.as_standardized() {
+            Some(TagStandard::Previous(refs)) => Some(refs.len()),
+            _ => None,
+        })
+        .sum();
+
+    if count > max {
+        return Err(Error::TooManyPreviousRefs { count, max });
+    }
+
+    Ok(())
+}
+
+/// Verify that a join request redeems an invite targeted to its author
+///
+/// The invite (kind 9009) must have a `p` tag with the invited user and a `code` tag.
+/// The join request (kind 9021) must be authored by the invited user and carry the same code.
+pub fn verify_targeted_invite(invite_event: &Event, join_event: &Event) -> Result<(), Error> {
+    if invite_event.kind != Kind::GroupCreateInvite {
+        return Err(Error::WrongKind(invite_event.kind));
+    }
+
+    if join_event.kind != Kind::GroupJoinRequest {
+        return Err(Error::WrongKind(join_event.kind));
+    }
+
+    let invited: &PublicKey = invite_event
+        .tags
+        .public_keys()
+        .next()
+        .ok_or_else(|| Error::MissingRequiredTag("p".into()))?;
+
+    if invited != &join_event.pubkey {
+        return Err(Error::UnexpectedAuthor {
+            expected: *invited,
+            found: join_event.pubkey,
+        });
+    }
+
+    let code: &str =
+        invite_code(invite_event).ok_or_else(|| Error::MissingRequiredTag("code".into()))?;
+
+    if invite_code(join_event) != Some(code) {
+        return Err(Error::InviteCodeMismatch);
+    }
+
+    Ok(())
+}
+
+/// Validate that the author of an invite (kind 9009) holds a role with the
+/// [`Permission::CreateInvite`] permission
+pub fn validate_invite_authority(
+    invite_event: &Event,
+    admins: &GroupAdmins,
+    roles: &GroupRoles,
+) -> Result<(), Error> {
+    if invite_event.kind != Kind::GroupCreateInvite {
+        return Err(Error::WrongKind(invite_event.kind));
+    }
+
+    let permission: Permission = Permission::CreateInvite;
+    let authorized: bool = admins.get(&invite_event.pubkey).is_some_and(|admin| {
+        roles
+            .effective_permissions(&admin.roles)
+            .contains(&permission)
+    });
+
+    if !authorized {
+        return Err(Error::MissingPermission {
+            public_key: invite_event.pubkey,
+            permission,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that a group metadata event was authored by the expected public key
+///
+/// Addressable group events (kinds 39000-39003) must be signed by the relay hosting the group.
+/// NIP-29 doesn't define a way to derive that key from the [`GroupId`],
+/// so the caller must provide it (e.g. the `self` field of the relay NIP-11 document).
+pub fn validate_metadata_author(event: &Event, expected: &PublicKey) -> Result<(), Error> {
+    if &event.pubkey != expected {
+        return Err(Error::UnexpectedAuthor {
+            expected: *expected,
+            found: event.pubkey,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validate that every admin is also listed as a member
+///
+/// Returns [`Error::AdminNotMember`] with the first admin missing from `members`.
+pub fn validate_admins_are_members(
+    admins: &GroupAdmins,
+    members: &GroupMembers,
+) -> Result<(), Error> {
+    match admins
+        .admins
+        .iter()
+        .find(|admin| !members.contains(&admin.public_key))
+    {
+        Some(admin) => Err(Error::AdminNotMember(admin.public_key)),
+        None => Ok(()),
+    }
+}
+
+/// Validate that the author of a leave request (kind 9022) is a member of the group
+pub fn validate_leave(leave_event: &Event, members: &GroupMembers) -> Result<(), Error> {
+    if leave_event.kind != Kind::GroupLeaveRequest {
+        return Err(Error::WrongKind(leave_event.kind));
+    }
+
+    if !members.contains(&leave_event.pubkey) {
+        return Err(Error::NotMember(leave_event.pubkey));
+    }
+
+    Ok(())
+}
+
+/// Check that the visibility of an event matches the privacy of its group
+///
+/// Events of private groups should carry the protected tag (`["-"]`), so that other relays
+/// refuse to republish them (<https://github.com/nostr-protocol/nips/blob/master/70.md>).
+/// Events of public groups are always accepted.
+///
+/// This check is advisory: NIP-29 doesn't require it and relays may not enforce it.
+pub fn validate_visibility(metadata: &GroupMetadata, event: &Event) -> Result<(), Error> {
+    if metadata.privacy.is_public() || event.is_protected() {
+        return Ok(());
+    }
+
+    Err(Error::UnprotectedEvent(event.id))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::nips::nip29::{Privacy, Role};
+    use crate::{EventBuilder, Keys, Kind, Tag, Url};
+
+    fn group_id() -> GroupId {
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        GroupId::new(url, "test-group".to_string()).unwrap()
+    }
+
+    fn state_set(keys: &Keys, group_id: GroupId) -> [Event; 4] {
+        [
+            EventBuilder::group_metadata(group_id.clone(), GroupMetadata::default()),
+            EventBuilder::group_admins(group_id.clone(), GroupAdmins::new()),
+            EventBuilder::group_members(group_id.clone(), GroupMembers::new()),
+            EventBuilder::group_roles(group_id, GroupRoles::new()),
+        ]
+        .map(|builder| builder.sign_with_keys(keys).unwrap())
+    }
+
+    #[test]
+    fn test_validate_metadata_author() {
+        let relay_keys = Keys::generate();
+        let other_keys = Keys::generate();
+
+        let event = EventBuilder::group_metadata(group_id(), GroupMetadata::default())
+            .sign_with_keys(&relay_keys)
+            .unwrap();
+
+        assert!(validate_metadata_author(&event, &relay_keys.public_key()).is_ok());
+        assert_eq!(
+            validate_metadata_author(&event, &other_keys.public_key()),
+            Err(Error::UnexpectedAuthor {
+                expected: other_keys.public_key(),
+                found: relay_keys.public_key(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_admins_are_members() {
+        let admin = Keys::generate().public_key();
+        let member = Keys::generate().public_key();
+
+        let admins = GroupAdmins::new().add_admin(GroupAdmin::new(admin, vec!["admin".into()]));
+
+        let members = GroupMembers::new().add_member(admin).add_member(member);
+        assert!(validate_admins_are_members(&admins, &members).is_ok());
+
+        let members = GroupMembers::new().add_member(member);
+        assert_eq!(
+            validate_admins_are_members(&admins, &members),
+            Err(Error::AdminNotMember(admin))
+        );
+    }
+
+    #[test]
+    fn test_addressable_identifier() {
+        let keys = Keys::generate();
+
+        let event = EventBuilder::group_metadata(group_id(), GroupMetadata::default())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(addressable_identifier(&event).unwrap(), "test-group");
+
+        // Missing `d` tag
+        let event = EventBuilder::new(Kind::GroupMetadata, "")
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            addressable_identifier(&event),
+            Err(Error::MissingRequiredTag("d".into()))
+        );
+
+        // Empty `d` tag
+        let event = EventBuilder::new(Kind::GroupMetadata, "")
+            .tag(Tag::identifier(""))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(addressable_identifier(&event).is_err());
+
+        // Not an addressable group event
+        let event = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            addressable_identifier(&event),
+            Err(Error::WrongKind(Kind::ChatMessage))
+        );
+    }
+
+    #[test]
+    fn test_validate_chain() {
+        let admin = Keys::generate();
+        let moderator = Keys::generate();
+        let stranger = Keys::generate();
+
+        let roles = GroupRoles::new()
+            .add_role(
+                Role::new("admin")
+                    .add_permission(Permission::AddUser)
+                    .add_permission(Permission::RemoveUser),
+            )
+            .add_role(Role::new("moderator").add_permission(Permission::RemoveUser));
+        let admins =
+            GroupAdmins::new().add_admin(GroupAdmin::new(admin.public_key(), vec!["admin".into()]));
+
+        // Not yet a moderator
+        let early = EventBuilder::group_remove_user(group_id(), stranger.public_key())
+            .sign_with_keys(&moderator)
+            .unwrap();
+        // Promote moderator
+        let promote = EventBuilder::group_put_user(
+            group_id(),
+            moderator.public_key(),
+            vec!["moderator".into()],
+        )
+        .sign_with_keys(&admin)
+        .unwrap();
+        // Now a moderator
+        let late = EventBuilder::group_remove_user(group_id(), stranger.public_key())
+            .sign_with_keys(&moderator)
+            .unwrap();
+        // Moderators can't delete the group
+        let overreach = EventBuilder::group_delete(group_id())
+            .sign_with_keys(&moderator)
+            .unwrap();
+        // Never an admin
+        let rogue = EventBuilder::group_delete(group_id())
+            .sign_with_keys(&stranger)
+            .unwrap();
+        // Not a moderation event
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&stranger)
+            .unwrap();
+
+        let checks = validate_chain(
+            &[
+                early.clone(),
+                promote.clone(),
+                late.clone(),
+                overreach.clone(),
+                rogue.clone(),
+                message,
+            ],
+            &admins,
+            &roles,
+        );
+        assert_eq!(
+            checks,
+            vec![
+                (early.id, AuthCheck::NotAdmin),
+                (promote.id, AuthCheck::Authorized),
+                (late.id, AuthCheck::Authorized),
+                (
+                    overreach.id,
+                    AuthCheck::MissingPermission(Permission::DeleteGroup)
+                ),
+                (rogue.id, AuthCheck::NotAdmin),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_conflict() {
+        let relay = Keys::generate();
+        let other = Keys::generate();
+
+        let a = EventBuilder::group_metadata(group_id(), GroupMetadata::default())
+            .sign_with_keys(&relay)
+            .unwrap();
+
+        // Same author: replaceable
+        let b = EventBuilder::group_metadata(group_id(), GroupMetadata::default())
+            .sign_with_keys(&relay)
+            .unwrap();
+        assert!(!events_conflict(&a, &b));
+
+        // Different authors
+        let c = EventBuilder::group_metadata(group_id(), GroupMetadata::default())
+            .sign_with_keys(&other)
+            .unwrap();
+        assert!(events_conflict(&a, &c));
+
+        // Different groups
+        let other_group = GroupId::new(group_id().relay_url, "other".to_string()).unwrap();
+        let d = EventBuilder::group_metadata(other_group, GroupMetadata::default())
+            .sign_with_keys(&other)
+            .unwrap();
+        assert!(!events_conflict(&a, &d));
+    }
+
+    #[test]
+    fn test_ids_collide() {
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let other_relay_url = Url::parse("wss://other.example.com").unwrap();
+
+        let a = GroupId::new(relay_url.clone(), "rust".to_string()).unwrap();
+        let b = GroupId::new(relay_url.clone(), "rust".to_string()).unwrap();
+        let c = GroupId::new(relay_url, "rust-devs".to_string()).unwrap();
+        let d = GroupId::new(other_relay_url, "rust".to_string()).unwrap();
+
+        assert!(ids_collide(&a, &b));
+        assert!(!ids_collide(&a, &c));
+        assert!(!ids_collide(&a, &d));
+    }
+
+    #[test]
+    fn test_verify_targeted_invite() {
+        let admin = Keys::generate();
+        let invited = Keys::generate();
+        let stranger = Keys::generate();
+
+        let invite = EventBuilder::group_create_invite(group_id())
+            .tags([
+                Tag::public_key(invited.public_key()),
+                Tag::invite_code("SECRET"),
+            ])
+            .sign_with_keys(&admin)
+            .unwrap();
+
+        let join = EventBuilder::group_join_with_code(group_id(), "SECRET")
+            .sign_with_keys(&invited)
+            .unwrap();
+        assert!(verify_targeted_invite(&invite, &join).is_ok());
+
+        // Wrong user
+        let join = EventBuilder::group_join_with_code(group_id(), "SECRET")
+            .sign_with_keys(&stranger)
+            .unwrap();
+        assert_eq!(
+            verify_targeted_invite(&invite, &join),
+            Err(Error::UnexpectedAuthor {
+                expected: invited.public_key(),
+                found: stranger.public_key(),
+            })
+        );
+
+        // Wrong code
+        let join = EventBuilder::group_join_with_code(group_id(), "GUESS")
+            .sign_with_keys(&invited)
+            .unwrap();
+        assert_eq!(
+            verify_targeted_invite(&invite, &join),
+            Err(Error::InviteCodeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_validate_previous_refs() {
+        let keys = Keys::generate();
+        let other_group = GroupId::new(group_id().relay_url, "other".to_string()).unwrap();
+
+        let same = EventBuilder::group_message(group_id(), "Same group")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let other = EventBuilder::group_message(other_group, "Other group")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let unknown = EventBuilder::group_message(group_id(), "Unknown")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let join = EventBuilder::group_join_request(group_id(), None::<String>)
+            .sign_with_keys(&keys)
+            .unwrap();
+        let known = [same.clone(), other.clone(), join.clone()];
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .with_previous_events(vec![same.id, unknown.id, join.id])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(validate_previous_refs(&message, &known).is_ok());
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .with_previous_events(vec![same.id, other.id])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            validate_previous_refs(&message, &known),
+            Err(Error::CrossGroupReference(
+                other.id.to_hex()[..8].to_string()
+            ))
+        );
+
+        // Too short references are skipped
+        let message = EventBuilder::new(Kind::ChatMessage, "Hello")
+            .tags([
+                Tag::group_id("test-group"),
+                Tag::custom(
+                    TagKind::Custom("previous".into()),
+                    ["", &other.id.to_hex()[..4]],
+                ),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(validate_previous_refs(&message, &known).is_ok());
+
+        // Ambiguous references are skipped
+        let mut twin: Event = other.clone();
+        let twin_id: String = format!("{}{}", &same.id.to_hex()[..8], &other.id.to_hex()[8..]);
+        twin.id = EventId::from_hex(&twin_id).unwrap();
+        let known = [twin, same.clone()];
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .with_previous_events(vec![same.id])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(validate_previous_refs(&message, &known).is_ok());
+    }
+
+    #[test]
+    fn test_validate_state_set() {
+        let keys = Keys::generate();
+
+        let [metadata, admins, members, roles] = state_set(&keys, group_id());
+        assert!(validate_state_set(&metadata, &admins, &members, &roles).is_ok());
+
+        // Mismatched `d` tag
+        let other_group = GroupId::new(group_id().relay_url, "other".to_string()).unwrap();
+        let [_, _, other_members, _] = state_set(&keys, other_group);
+        assert_eq!(
+            validate_state_set(&metadata, &admins, &other_members, &roles),
+            Err(Error::GroupMismatch {
+                expected: "test-group".into(),
+                found: "other".into(),
+            })
+        );
+
+        // Wrong kind
+        assert_eq!(
+            validate_state_set(&metadata, &members, &admins, &roles),
+            Err(Error::WrongKind(Kind::GroupMembers))
+        );
+    }
+
+    #[test]
+    fn test_validate_invite_authority() {
+        let admin = Keys::generate();
+        let moderator = Keys::generate();
+        let stranger = Keys::generate();
+
+        let roles = GroupRoles::new()
+            .add_role(Role::new("admin").add_permission(Permission::CreateInvite))
+            .add_role(Role::new("moderator").add_permission(Permission::RemoveUser));
+        let admins = GroupAdmins::new()
+            .add_admin(GroupAdmin::new(admin.public_key(), vec!["admin".into()]))
+            .add_admin(GroupAdmin::new(
+                moderator.public_key(),
+                vec!["moderator".into()],
+            ));
+
+        let invite = EventBuilder::group_create_invite(group_id())
+            .sign_with_keys(&admin)
+            .unwrap();
+        assert!(validate_invite_authority(&invite, &admins, &roles).is_ok());
+
+        for keys in [moderator, stranger] {
+            let invite = EventBuilder::group_create_invite(group_id())
+                .sign_with_keys(&keys)
+                .unwrap();
+            assert_eq!(
+                validate_invite_authority(&invite, &admins, &roles),
+                Err(Error::MissingPermission {
+                    public_key: keys.public_key(),
+                    permission: Permission::CreateInvite,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_previous_count() {
+        let keys = Keys::generate();
+
+        let ids: Vec<EventId> = (0..3)
+            .map(|i| {
+                EventBuilder::group_message(group_id(), format!("Message {i}"))
+                    .unwrap()
+                    .sign_with_keys(&keys)
+                    .unwrap()
+                    .id
+            })
+            .collect();
+
+        let event = EventBuilder::group_message(group_id(), "Reply")
+            .unwrap()
+            .with_previous_events(ids)
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert!(validate_previous_count(&event, 3).is_ok());
+        assert!(validate_previous_count(&event, 50).is_ok());
+        assert_eq!(
+            validate_previous_count(&event, 2),
+            Err(Error::TooManyPreviousRefs { count: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn test_validate_delete_target() {
+        let keys = Keys::generate();
+        let other_group = GroupId::new(group_id().relay_url, "other".to_string()).unwrap();
+
+        let message = EventBuilder::group_message(group_id(), "Spam")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let other_message = EventBuilder::group_message(other_group, "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let delete = EventBuilder::group_delete_event(group_id(), message.id)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(validate_delete_target(&delete, &message).is_ok());
+
+        let delete = EventBuilder::group_delete_event(group_id(), other_message.id)
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            validate_delete_target(&delete, &other_message),
+            Err(Error::GroupMismatch {
+                expected: "test-group".into(),
+                found: "other".into(),
+            })
+        );
+
+        // Not a delete-event
+        assert_eq!(
+            validate_delete_target(&message, &other_message),
+            Err(Error::WrongKind(Kind::ChatMessage))
+        );
+    }
+
+    #[test]
+    fn test_validate_create_event() {
+        let keys = Keys::generate();
+        let metadata = GroupMetadata {
+            name: Some("Rust devs".to_string()),
+            privacy: Privacy::Private,
+            ..Default::default()
+        };
+
+        let event = EventBuilder::group_create(group_id(), metadata.clone())
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(validate_create_event(&event).unwrap(), metadata);
+
+        // Missing `h` tag
+        let event = EventBuilder::new(Kind::GroupCreate, "")
+            .tags(Vec::<Tag>::from(metadata))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            validate_create_event(&event),
+            Err(Error::MissingRequiredTag("h".into()))
+        );
+
+        // Unparseable metadata
+        let event = EventBuilder::group_create(group_id(), GroupMetadata::default())
+            .tag(Tag::custom(TagKind::Custom("privacy".into()), ["secret"]))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(matches!(
+            validate_create_event(&event),
+            Err(Error::InvalidPrivacy(..))
+        ));
+    }
+
+    #[test]
+    fn test_validate_invite_code() {
+        assert!(validate_invite_code("INVITE-123_abc.~").is_ok());
+
+        assert_eq!(
+            validate_invite_code(""),
+            Err(Error::InvalidInviteCode(String::new()))
+        );
+        assert!(validate_invite_code("with space").is_err());
+        assert!(validate_invite_code("a/b?c=d").is_err());
+        assert!(validate_invite_code("café").is_err());
+    }
+
+    #[test]
+    fn test_validate_leave() {
+        let member = Keys::generate();
+        let stranger = Keys::generate();
+        let members = GroupMembers::new().add_member(member.public_key());
+
+        let leave = EventBuilder::group_leave_request(group_id())
+            .sign_with_keys(&member)
+            .unwrap();
+        assert_eq!(validate_leave(&leave, &members), Ok(()));
+
+        let leave = EventBuilder::group_leave_request(group_id())
+            .sign_with_keys(&stranger)
+            .unwrap();
+        assert_eq!(
+            validate_leave(&leave, &members),
+            Err(Error::NotMember(stranger.public_key()))
+        );
+
+        let join = EventBuilder::group_join_request(group_id(), None::<String>)
+            .sign_with_keys(&member)
+            .unwrap();
+        assert_eq!(
+            validate_leave(&join, &members),
+            Err(Error::WrongKind(Kind::GroupJoinRequest))
+        );
+    }
+
+    #[test]
+    fn test_validate_visibility() {
+        let keys = Keys::generate();
+        let public = GroupMetadata::default();
+        let private = GroupMetadata {
+            privacy: Privacy::Private,
+            ..Default::default()
+        };
+
+        let message = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .sign_with_keys(&keys)
+            .unwrap();
+        let protected = EventBuilder::group_message(group_id(), "Hello")
+            .unwrap()
+            .tag(Tag::protected())
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(validate_visibility(&public, &message), Ok(()));
+        assert_eq!(validate_visibility(&public, &protected), Ok(()));
+
+        assert_eq!(
+            validate_visibility(&private, &message),
+            Err(Error::UnprotectedEvent(message.id))
+        );
+        assert_eq!(validate_visibility(&private, &protected), Ok(()));
+    }
+}